@@ -0,0 +1,64 @@
+//! How the cost of a tick and of formatting the counter grows with the
+//! counter. Run with `cargo bench` for the default `Decimal` backend and with
+//! `cargo bench --features big-float` for `BigFloat`, whose numbers should
+//! stay flat across sizes.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use idle_game::env::{Env, ManualClock};
+use idle_game::game::{reducer, Msg, State};
+use idle_game::locale::Locale;
+use idle_game::notation::Notation;
+use idle_game::number::{BigFloat, Decimal, Number};
+
+const EXPONENTS: &[u32] = &[10, 100, 1_000, 10_000];
+
+fn counter(exponent: u32) -> Number {
+    format!("1.2345e{}", exponent).parse().unwrap()
+}
+
+fn tick(c: &mut Criterion) {
+    let env = Env::in_memory(ManualClock::default());
+    let mut group = c.benchmark_group("tick");
+    for &exponent in EXPONENTS {
+        let state = State {
+            counter: counter(exponent),
+            lifetime_earned: counter(exponent),
+            ..State::new(0.0)
+        };
+        group.bench_with_input(BenchmarkId::from_parameter(exponent), &state, |b, state| {
+            b.iter(|| reducer(black_box(state), Msg::Tick(1000), &env))
+        });
+    }
+    group.finish();
+}
+
+fn format(c: &mut Criterion) {
+    let mut group = c.benchmark_group("format");
+    for &exponent in EXPONENTS {
+        let counter = counter(exponent);
+        group.bench_with_input(BenchmarkId::from_parameter(exponent), &counter, |b, counter| {
+            b.iter(|| Notation::Short.format(black_box(counter), Locale::En))
+        });
+    }
+    group.finish();
+}
+
+/// Both backends side by side, whichever one the game is built with.
+fn add(c: &mut Criterion) {
+    let mut group = c.benchmark_group("add");
+    for &exponent in EXPONENTS {
+        let value = format!("1.2345e{}", exponent);
+        let decimal: Decimal = value.parse().unwrap();
+        let float: BigFloat = value.parse().unwrap();
+        group.bench_with_input(BenchmarkId::new("Decimal", exponent), &decimal, |b, n| {
+            b.iter(|| black_box(n) + black_box(n))
+        });
+        group.bench_with_input(BenchmarkId::new("BigFloat", exponent), &float, |b, n| {
+            b.iter(|| black_box(n) + black_box(n))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, tick, format, add);
+criterion_main!(benches);
@@ -0,0 +1,158 @@
+//! Milestones the player is rewarded for reaching. The table is checked after
+//! every state transition; unlocked achievements are kept in the save and
+//! some grant a small permanent production bonus.
+
+use crate::number::Number;
+use crate::game::State;
+use crate::locale::Text;
+
+/// What has to be true of the game for an achievement to unlock.
+pub enum Condition {
+    CounterAtLeast(u64),
+    ProductionAtLeast(u64),
+    UpgradeLevelAtLeast(u32),
+    GeneratorsOwnedAtLeast(u32),
+    /// Came back after at least this many seconds away.
+    OfflineSecondsAtLeast(f64),
+    PrestigePointsAtLeast(u64),
+}
+
+impl Condition {
+    fn is_met(&self, state: &State) -> bool {
+        match *self {
+            Condition::CounterAtLeast(amount) => state.counter >= Number::from(amount),
+            Condition::ProductionAtLeast(amount) => state.production() >= Number::from(amount),
+            Condition::UpgradeLevelAtLeast(level) => state.upgrade_level >= level,
+            Condition::GeneratorsOwnedAtLeast(count) => {
+                state.generators.iter().sum::<u32>() >= count
+            }
+            Condition::OfflineSecondsAtLeast(seconds) => state
+                .offline_report
+                .as_ref()
+                .is_some_and(|report| report.away_seconds >= seconds),
+            Condition::PrestigePointsAtLeast(points) => {
+                state.prestige_points >= Number::from(points)
+            }
+        }
+    }
+}
+
+pub struct Achievement {
+    /// Stable key stored in saves; never rename.
+    pub id: &'static str,
+    pub name: Text,
+    pub description: Text,
+    pub condition: Condition,
+    /// Production bonus while unlocked, in percent.
+    pub bonus_percent: u32,
+}
+
+pub const ACHIEVEMENTS: &[Achievement] = &[
+    Achievement {
+        id: "counter_1k",
+        name: Text::AchievementPocketChange,
+        description: Text::AchievementPocketChangeGoal,
+        condition: Condition::CounterAtLeast(1_000),
+        bonus_percent: 0,
+    },
+    Achievement {
+        id: "counter_1m",
+        name: Text::AchievementMillionaire,
+        description: Text::AchievementMillionaireGoal,
+        condition: Condition::CounterAtLeast(1_000_000),
+        bonus_percent: 2,
+    },
+    Achievement {
+        id: "production_1b",
+        name: Text::AchievementIndustrialScale,
+        description: Text::AchievementIndustrialScaleGoal,
+        condition: Condition::ProductionAtLeast(1_000_000_000),
+        bonus_percent: 5,
+    },
+    Achievement {
+        id: "upgrades_10",
+        name: Text::AchievementTinkerer,
+        description: Text::AchievementTinkererGoal,
+        condition: Condition::UpgradeLevelAtLeast(10),
+        bonus_percent: 2,
+    },
+    Achievement {
+        id: "generators_100",
+        name: Text::AchievementCollector,
+        description: Text::AchievementCollectorGoal,
+        condition: Condition::GeneratorsOwnedAtLeast(100),
+        bonus_percent: 2,
+    },
+    Achievement {
+        id: "offline_8h",
+        name: Text::AchievementGoodNightsSleep,
+        description: Text::AchievementGoodNightsSleepGoal,
+        condition: Condition::OfflineSecondsAtLeast(8.0 * 60.0 * 60.0),
+        bonus_percent: 1,
+    },
+    Achievement {
+        id: "prestige_1",
+        name: Text::AchievementBornAgain,
+        description: Text::AchievementBornAgainGoal,
+        condition: Condition::PrestigePointsAtLeast(1),
+        bonus_percent: 5,
+    },
+];
+
+pub fn find(id: &str) -> Option<&'static Achievement> {
+    ACHIEVEMENTS.iter().find(|achievement| achievement.id == id)
+}
+
+/// Unlock every achievement whose condition `state` now meets, queueing a
+/// toast for each.
+pub fn unlock_new(state: &mut State) {
+    for achievement in ACHIEVEMENTS {
+        if !state.has_achievement(achievement.id) && achievement.condition.is_met(state) {
+            state.achievements.push(achievement.id.to_string());
+            state.achievement_toasts.push(achievement.id.to_string());
+        }
+    }
+}
+
+/// Total production bonus of the unlocked achievements, in percent.
+pub fn bonus_percent(unlocked: &[String]) -> u32 {
+    unlocked
+        .iter()
+        .filter_map(|id| find(id))
+        .map(|achievement| achievement.bonus_percent)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_are_unique() {
+        for (i, achievement) in ACHIEVEMENTS.iter().enumerate() {
+            assert!(ACHIEVEMENTS[i + 1..].iter().all(|other| other.id != achievement.id));
+        }
+    }
+
+    #[test]
+    fn unlocks_once_with_a_toast() {
+        let mut state = State {
+            counter: Number::from(1_500u32),
+            ..State::new(0.0)
+        };
+        unlock_new(&mut state);
+        assert_eq!(state.achievements, vec!["counter_1k"]);
+        assert_eq!(state.achievement_toasts, vec!["counter_1k"]);
+
+        state.achievement_toasts.clear();
+        unlock_new(&mut state);
+        assert_eq!(state.achievements, vec!["counter_1k"]);
+        assert!(state.achievement_toasts.is_empty());
+    }
+
+    #[test]
+    fn bonuses_add_up_and_ignore_unknown_ids() {
+        let unlocked = ["counter_1m", "prestige_1", "retired"].map(String::from);
+        assert_eq!(bonus_percent(&unlocked), 7);
+    }
+}
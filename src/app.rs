@@ -1,170 +1,230 @@
 use yew::prelude::*;
-use gloo::timers::callback::Interval;
-use num_bigint::BigUint;
-use num_traits::{Zero, One};
+use gloo::events::EventListener;
+use gloo::render::{request_animation_frame, AnimationFrame};
+use gloo::timers::callback::{Interval, Timeout};
+use num_traits::Zero;
+use std::cell::RefCell;
+use std::ops::Deref;
 use std::rc::Rc;
-use web_sys::console;
+use web_sys::{console, HtmlSelectElement, HtmlTextAreaElement};
 use yew::Reducible;
-use gloo::storage::{LocalStorage, Storage};
-use serde::{Serialize, Deserialize};
 
-const SUFFIXES: &[&str] = &["", "K", "M", "B", "T", "Qa", "Qi", "Sx", "Sp", "Oc"];
+use idle_game::achievements::{self, ACHIEVEMENTS};
+use idle_game::autosave::{SaveStatus, AUTOSAVE_INTERVALS};
+use idle_game::number::Number;
+use idle_game::env::Env;
+use idle_game::game::{BuyAmount, Msg, Purchase, State, BUY_AMOUNTS};
+use idle_game::generators::GENERATORS;
+use idle_game::journal::{Journal, JournalClock};
+use idle_game::locale::{self, Locale, Text, LOCALES};
+use idle_game::notation::NOTATIONS;
+use idle_game::slots;
+use idle_game::tick::TickTimer;
+use idle_game::undo::UndoAction;
 
-fn format_number(num: &BigUint) -> String {
-    let num_str = num.to_string();
-    let len = num_str.len();
-    
-    if len <= 3 {
-        return num_str;
-    }
-    
-    if len / 3 >= SUFFIXES.len() {
-        // Use scientific notation for numbers beyond our suffix list
-        let first_digit = &num_str[..1];
-        let second_digits = num_str.get(1..3).unwrap_or("0");
-        return format!("{}.{}e{}", first_digit, second_digits, len - 1);
-    }
-    
-    let suffix_index = (len - 1) / 3;
-    let offset = len - (suffix_index * 3);
-    
-    let main_digits = &num_str[..offset];
-    let decimal_digits = num_str.get(offset..offset + 2).unwrap_or("00");
-    
-    if decimal_digits == "00" {
-        format!("{}{}", main_digits, SUFFIXES[suffix_index])
-    } else {
-        format!("{}.{}{}", main_digits, decimal_digits, SUFFIXES[suffix_index])
+use crate::backups_tab::BackupsTab;
+use crate::confirm_dialog::ConfirmDialog;
+use crate::slot_picker::SlotPicker;
+use crate::stats_tab::StatsTab;
+use crate::sync_panel::SyncPanel;
+use crate::upgrade_tree::UpgradeTree;
+use crate::web::{browser_locale, StoreKind, WriteErrors, STORE_KINDS};
+
+#[derive(Properties)]
+pub struct AppProps {
+    pub env: Rc<Env>,
+    /// The clock of `env`, which the journal holds still while it reduces.
+    pub clock: Rc<JournalClock>,
+    pub store_kind: StoreKind,
+    /// Failed writes of `env`'s store that it could only find out about later.
+    pub write_errors: WriteErrors,
+}
+
+impl PartialEq for AppProps {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.env, &other.env)
+            && Rc::ptr_eq(&self.clock, &other.clock)
+            && self.store_kind == other.store_kind
+            && self.write_errors == other.write_errors
     }
 }
 
-mod big_uint_serde {
-    use super::*;
-    use serde::{Serializer, Deserializer};
+/// The game state plus the environment its reducer runs against, and the
+/// journal of every message it was sent.
+pub struct Model {
+    game: State,
+    env: Rc<Env>,
+    clock: Rc<JournalClock>,
+    journal: Rc<RefCell<Journal>>,
+}
 
-    pub fn serialize<S>(num: &BigUint, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        num.to_string().serialize(serializer)
+impl Model {
+    fn start(env: Rc<Env>, clock: Rc<JournalClock>, slot: usize) -> Self {
+        let game = State::load(&env, slot)
+            .unwrap_or_else(|e| {
+                console::log_1(&format!("Load error: {}", e).into());
+                None
+            })
+            .unwrap_or_else(|| State {
+                slot,
+                ..State::new(env.clock.now())
+            });
+        let journal = Rc::new(RefCell::new(Journal::start(&game, &env)));
+        Self {
+            game,
+            env,
+            clock,
+            journal,
+        }
     }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<BigUint, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        s.parse().map_err(serde::de::Error::custom)
+    /// A replay code of this game's journal, for a bug report.
+    fn export_replay(&self) -> String {
+        self.journal.borrow().export(&self.game)
     }
 }
 
-#[derive(Clone, PartialEq, Serialize, Deserialize)]
-pub struct State {
-    #[serde(with = "big_uint_serde")]
-    counter: BigUint,
-    #[serde(with = "big_uint_serde")]
-    production: BigUint,
-    last_save: f64,
-    last_saved_at: Option<f64>,
-}
-
-#[derive(Clone)]
-pub enum Msg {
-    Tick,
-    UpgradeProduction,
-    Save,
-    Load,
-    Reset,
-}
+impl Deref for Model {
+    type Target = State;
 
-fn reducer(state: &State, msg: Msg) -> State {
-    match msg {
-        Msg::Tick => {
-            // Update counter by adding production.
-            let new_counter = state.counter.clone() + state.production.clone();
-            State {
-                counter: new_counter,
-                production: state.production.clone(),
-                last_save: state.last_save,
-                last_saved_at: state.last_saved_at,
-            }
-        }
-        Msg::UpgradeProduction => {
-            // Double the production value.
-            let new_production = state.production.clone() * 2u32;
-            State {
-                counter: state.counter.clone(),
-                production: new_production,
-                last_save: state.last_save,
-                last_saved_at: state.last_saved_at,
-            }
-        }
-        Msg::Save => {
-            state.save().unwrap_or_else(|e| console::log_1(&e.into()));
-            state.clone()
-        }
-        Msg::Load => {
-            State::load().unwrap_or_else(|| state.clone())
-        }
-        Msg::Reset => State::new(),
+    fn deref(&self) -> &State {
+        &self.game
     }
 }
 
-impl Reducible for State {
+impl Reducible for Model {
     type Action = Msg;
 
     fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
-        Rc::new(reducer(&self, action))
+        let game = self
+            .journal
+            .borrow_mut()
+            .dispatch(&self.game, action, &self.env, &self.clock);
+        Rc::new(Model {
+            game,
+            env: self.env.clone(),
+            clock: self.clock.clone(),
+            journal: self.journal.clone(),
+        })
     }
 }
 
-impl State {
-    fn new() -> Self {
-        Self {
-            counter: BigUint::zero(),
-            production: BigUint::one(),
-            last_save: js_sys::Date::now(),
-            last_saved_at: None,
-        }
-    }
-
-    fn save(&self) -> Result<(), String> {
-        let mut state = self.clone();
-        state.last_saved_at = Some(js_sys::Date::now());
-        LocalStorage::set("idle_game_save", &state).map_err(|e| e.to_string())
-    }
+/// Shows the slot picker until a slot is chosen, then that slot's game, under
+/// a language picker shared by both.
+#[function_component(App)]
+pub fn app(props: &AppProps) -> Html {
+    let active_slot = use_state(|| None::<usize>);
+    let locale = {
+        let env = props.env.clone();
+        use_state(move || {
+            locale::load_preference(env.store.as_ref())
+                .unwrap_or_else(|e| {
+                    console::log_1(&format!("Locale preference error: {}", e).into());
+                    None
+                })
+                .or_else(browser_locale)
+                .unwrap_or_default()
+        })
+    };
 
-    fn load() -> Option<Self> {
-        LocalStorage::get("idle_game_save").ok().map(|mut state: State| {
-            // Calculate offline progress
-            let now = js_sys::Date::now();
-            let elapsed_seconds = ((now - state.last_save) / 1000.0) as u32;
-            if elapsed_seconds > 0 {
-                state.counter += &state.production * elapsed_seconds;
+    let on_locale_change = {
+        let env = props.env.clone();
+        let locale = locale.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Some(&picked) = LOCALES.get(select.selected_index() as usize) {
+                if let Err(e) = locale::save_preference(env.store.as_ref(), picked) {
+                    console::log_1(&format!("Locale preference error: {}", e).into());
+                }
+                locale.set(picked);
             }
-            state.last_save = now;
-            state
         })
-    }
+    };
 
-    fn format_last_saved(&self) -> String {
-        self.last_saved_at.map_or("Never".to_string(), |timestamp| {
-            let seconds_ago = (js_sys::Date::now() - timestamp) / 1000.0;
-            if seconds_ago < 60.0 {
-                "Just now".to_string()
-            } else if seconds_ago < 3600.0 {
-                format!("{:.0} minutes ago", seconds_ago / 60.0)
-            } else {
-                format!("{:.1} hours ago", seconds_ago / 3600.0)
+    let on_store_change = Callback::from(move |e: Event| {
+        let select: HtmlSelectElement = e.target_unchecked_into();
+        if let Some(&picked) = STORE_KINDS.get(select.selected_index() as usize) {
+            picked.switch_to();
+        }
+    });
+
+    let view = match *active_slot {
+        Some(slot) => {
+            let on_exit = {
+                let active_slot = active_slot.clone();
+                Callback::from(move |_| active_slot.set(None))
+            };
+            html! {
+                <Game
+                    key={slot}
+                    env={props.env.clone()}
+                    clock={props.clock.clone()}
+                    store_kind={props.store_kind}
+                    write_errors={props.write_errors.clone()}
+                    {slot}
+                    locale={*locale}
+                    {on_exit} />
             }
-        })
+        }
+        None => {
+            let on_select = Callback::from(move |slot| active_slot.set(Some(slot)));
+            html! { <SlotPicker env={props.env.clone()} locale={*locale} {on_select} /> }
+        }
+    };
+
+    html! {
+        <>
+            <div class="p-4 max-w-2xl mx-auto flex items-center justify-end gap-2 text-sm text-gray-500">
+                <label for="locale">{ locale.text(Text::Language) }</label>
+                <select id="locale" class="p-1 rounded border" onchange={on_locale_change}>
+                    { for LOCALES.iter().map(|&option| html! {
+                        <option selected={option == *locale}>{ option.label() }</option>
+                    }) }
+                </select>
+                <label for="store">{ locale.text(Text::Storage) }</label>
+                <select id="store" class="p-1 rounded border" onchange={on_store_change}>
+                    { for STORE_KINDS.iter().map(|&option| html! {
+                        <option selected={option == props.store_kind}>{ option.label() }</option>
+                    }) }
+                </select>
+            </div>
+            { view }
+        </>
     }
 }
 
-#[function_component(App)]
-pub fn app() -> Html {
-    let state = use_reducer(|| State::load().unwrap_or_else(State::new));
-    let interval_key = use_state(|| 0);
+#[derive(Properties)]
+pub struct GameProps {
+    pub env: Rc<Env>,
+    pub clock: Rc<JournalClock>,
+    pub store_kind: StoreKind,
+    pub write_errors: WriteErrors,
+    pub slot: usize,
+    pub locale: Locale,
+    /// Called after the game is saved to go back to the slot picker.
+    pub on_exit: Callback<()>,
+}
+
+impl PartialEq for GameProps {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.env, &other.env)
+            && Rc::ptr_eq(&self.clock, &other.clock)
+            && self.store_kind == other.store_kind
+            && self.write_errors == other.write_errors
+            && self.slot == other.slot
+            && self.locale == other.locale
+            && self.on_exit == other.on_exit
+    }
+}
+
+#[function_component(Game)]
+fn game(props: &GameProps) -> Html {
+    let state = {
+        let env = props.env.clone();
+        let clock = props.clock.clone();
+        let slot = props.slot;
+        use_reducer(move || Model::start(env, clock, slot))
+    };
     let time_update = use_state(|| 0);
 
     // Add this effect for updating the time display
@@ -181,97 +241,693 @@ pub fn app() -> Html {
         );
     }
 
-    // Setup autosave interval
+    // Tick about once a second, crediting however long really passed. Hidden
+    // tabs get their timers throttled, so settle up when the tab hides and
+    // catch up as soon as it shows; all of that counts as play.
+    let tick_timer = {
+        let env = props.env.clone();
+        use_mut_ref(move || TickTimer::new(env.clock.monotonic()))
+    };
     {
         let state = state.clone();
+        let tick_timer = tick_timer.clone();
         use_effect_with_deps(
             move |_| {
-                let interval = Interval::new(5000, move || {
-                    if let Err(e) = state.save() {
-                        console::log_1(&format!("Save error: {}", e).into());
-                    }
+                let interval = {
+                    let state = state.clone();
+                    let tick_timer = tick_timer.clone();
+                    Interval::new(1000, move || {
+                        let msg = tick_timer.borrow_mut().tick(state.env.clock.monotonic());
+                        state.dispatch(msg);
+                    })
+                };
+                let document = gloo::utils::document();
+                let on_visibility_change = EventListener::new(&document, "visibilitychange", move |_| {
+                    let hidden = gloo::utils::document().hidden();
+                    let msg = tick_timer
+                        .borrow_mut()
+                        .visibility_changed(state.env.clock.monotonic(), hidden);
+                    state.dispatch(msg);
                 });
-                move || drop(interval)
+                move || {
+                    drop(interval);
+                    drop(on_visibility_change);
+                }
             },
-            *interval_key,
+            (),
         );
     }
 
-    // Setup tick interval
+    // Autosave through the reducer, so it always sees the latest state and
+    // skips the write when nothing changed. Also save as the page is hidden
+    // or closed, when the interval may never fire again. Registered after the
+    // tick listener so the time up to hiding is credited first.
     {
+        let seconds = state.autosave_seconds;
         let state = state.clone();
         use_effect_with_deps(
-            move |_| {
-                let interval = Interval::new(1000, move || {
-                    console::log_1(&"tick".into());
-                    state.dispatch(Msg::Tick);
+            move |&seconds| {
+                let interval = {
+                    let state = state.clone();
+                    Interval::new(seconds * 1000, move || state.dispatch(Msg::Autosave))
+                };
+                let on_visibility_change = {
+                    let state = state.clone();
+                    EventListener::new(&gloo::utils::document(), "visibilitychange", move |_| {
+                        if gloo::utils::document().hidden() {
+                            state.dispatch(Msg::Autosave);
+                        }
+                    })
+                };
+                let on_page_hide = EventListener::new(&gloo::utils::window(), "pagehide", move |_| {
+                    state.dispatch(Msg::Autosave)
                 });
-                move || drop(interval)
+                move || {
+                    drop(interval);
+                    drop(on_visibility_change);
+                    drop(on_page_hide);
+                }
             },
-            *interval_key,
+            seconds,
         );
     }
 
-    // Update interval_key when loading
-    let on_load = {
-        let interval_key = interval_key.clone();
+    // Writes the store finds out about too late to fail the save still
+    // show as a save error, and leave the game to the next autosave.
+    {
+        let state = state.clone();
+        use_effect_with_deps(
+            move |write_errors: &WriteErrors| {
+                write_errors.listen(Callback::from(move |e| state.dispatch(Msg::WriteFailed(e))));
+                let write_errors = write_errors.clone();
+                move || write_errors.stop_listening()
+            },
+            props.write_errors.clone(),
+        );
+    }
+
+    let buy_amount_handle = use_state(BuyAmount::default);
+    let tab = use_state(Tab::default);
+
+    // Resetting, loading, importing and restoring wait for a confirmation.
+    let confirming = use_state(|| None::<Msg>);
+    let confirm = |msg: Msg| {
+        let confirming = confirming.clone();
+        Callback::from(move |_: MouseEvent| confirming.set(Some(msg.clone())))
+    };
+
+    let export_code = use_state(|| None::<String>);
+    let on_export = {
+        let export_code = export_code.clone();
         let state = state.clone();
+        Callback::from(move |_| export_code.set(Some(state.export(state.env.clock.now()))))
+    };
+
+    let replay_code = use_state(|| None::<String>);
+    let on_export_replay = {
+        let replay_code = replay_code.clone();
+        let state = state.clone();
+        Callback::from(move |_| replay_code.set(Some(state.export_replay())))
+    };
+
+    let import_text = use_state(String::new);
+    let on_import_input = {
+        let import_text = import_text.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlTextAreaElement = e.target_unchecked_into();
+            import_text.set(input.value());
+        })
+    };
+    let on_import = {
+        let import_text = import_text.clone();
+        let confirming = confirming.clone();
+        Callback::from(move |_| confirming.set(Some(Msg::Import((*import_text).clone()))))
+    };
+
+    let on_switch_slot = {
+        let state = state.clone();
+        let on_exit = props.on_exit.clone();
         Callback::from(move |_| {
-            state.dispatch(Msg::Load);
-            interval_key.set(*interval_key + 1); // Force interval recreation
+            state.dispatch(Msg::Save);
+            on_exit.emit(());
         })
     };
 
+    let on_notation_change = {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Some(&notation) = NOTATIONS.get(select.selected_index() as usize) {
+                state.dispatch(Msg::SetNotation(notation));
+            }
+        })
+    };
+
+    let on_autosave_change = {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Some(&seconds) = AUTOSAVE_INTERVALS.get(select.selected_index() as usize) {
+                state.dispatch(Msg::SetAutosaveInterval(seconds));
+            }
+        })
+    };
+
+    let notation = state.notation;
+    let locale = props.locale;
+    let format = |num: &Number| notation.format(num, locale);
+    let pending_prestige = state.pending_prestige_points();
+
+    let buy_amount = *buy_amount_handle;
+    let bulk_label = |purchase, one: Text, many: Text| {
+        state.bulk_price(purchase, buy_amount).map(|(quantity, price)| {
+            if quantity > 1 {
+                locale.fill(many, &[&locale.number(&quantity.to_string()), &format(&price)])
+            } else {
+                locale.fill(one, &[&format(&price)])
+            }
+        })
+    };
+    let buy = |purchase| create_dispatch_callback(state.clone(), Msg::BuyMany(purchase, buy_amount));
+
+    let upgrade_label = bulk_label(Purchase::ProductionUpgrade, Text::UpgradeCost, Text::UpgradeManyCost)
+        .unwrap_or_else(|| locale.text(Text::UpgradeMaxed).to_string());
+
+    let on_restore_backup = {
+        let confirming = confirming.clone();
+        Callback::from(move |saved_at| confirming.set(Some(Msg::RestoreBackup(saved_at))))
+    };
+    let on_confirm = {
+        let state = state.clone();
+        let confirming = confirming.clone();
+        let tab = tab.clone();
+        Callback::from(move |_| {
+            if let Some(msg) = (*confirming).clone() {
+                state.dispatch(msg);
+                tab.set(Tab::Play);
+            }
+            confirming.set(None);
+        })
+    };
+    let on_cancel = {
+        let confirming = confirming.clone();
+        Callback::from(move |_| confirming.set(None))
+    };
+
+    let now = state.env.clock.now();
+    let undo = state.undo.clone().filter(|undo| undo.is_open(now));
+
+    let on_buy_upgrade = {
+        let state = state.clone();
+        Callback::from(move |id| state.dispatch(Msg::BuyUpgrade(id)))
+    };
+
+    let click_upgrade_label =
+        bulk_label(Purchase::ClickUpgrade, Text::ClickUpgradeCost, Text::ClickUpgradeManyCost)
+            .unwrap_or_else(|| locale.fill(Text::ClickUpgradeCost, &["-"]));
+
     html! {
         <div class="p-4 max-w-2xl mx-auto">
-            <h1 class="text-3xl font-bold mb-4 text-center">{ "Idle Game with Big Numbers" }</h1>
+            <h1 class="text-3xl font-bold mb-4 text-center">{ locale.text(Text::Title) }</h1>
             
-            <div class="bg-gray-100 rounded-lg p-4 mb-4">
-                <div class="grid grid-cols-2 gap-4">
-                    <div class="bg-white p-3 rounded shadow">
-                        <div class="text-gray-600 text-sm">{ "Counter" }</div>
-                        <div class="text-2xl font-bold">{ format_number(&(*state).counter) }</div>
+            <div class="flex gap-2 mb-4 border-b">
+                { for TABS.iter().map(|&option| {
+                    let class = if option == *tab {
+                        "px-4 py-2 font-bold border-b-2 border-blue-500"
+                    } else {
+                        "px-4 py-2 text-gray-600 hover:text-black"
+                    };
+                    let onclick = {
+                        let tab = tab.clone();
+                        Callback::from(move |_| tab.set(option))
+                    };
+                    html! { <button {class} {onclick}>{ locale.text(option.label()) }</button> }
+                }) }
+            </div>
+
+            if *tab == Tab::Stats {
+                <StatsTab state={(**state).clone()} {now} {locale} />
+            } else if *tab == Tab::Backups {
+                <BackupsTab
+                    env={state.env.clone()}
+                    slot={props.slot}
+                    {notation}
+                    {locale}
+                    on_restore={on_restore_backup} />
+            } else {
+                <div class="bg-gray-100 rounded-lg p-4 mb-4">
+                    <div class="grid grid-cols-2 gap-4">
+                        <div class="bg-white p-3 rounded shadow">
+                            <div class="text-gray-600 text-sm">{ locale.text(Text::Counter) }</div>
+                            <AnimatedCounter
+                                env={state.env.clone()}
+                                state={(**state).clone()}
+                                tick_timer={tick_timer.clone()}
+                                {locale}
+                            />
+                        </div>
+                        <div class="bg-white p-3 rounded shadow">
+                            <div class="text-gray-600 text-sm">{ locale.text(Text::ProductionPerSecond) }</div>
+                            <div class="text-2xl font-bold">{ format(&state.production()) }</div>
+                        </div>
                     </div>
-                    <div class="bg-white p-3 rounded shadow">
-                        <div class="text-gray-600 text-sm">{ "Production per second" }</div>
-                        <div class="text-2xl font-bold">{ format_number(&(*state).production) }</div>
+                </div>
+
+                <div class="bg-gray-100 rounded-lg p-4 mb-4 flex flex-col items-center gap-2">
+                    <button
+                        class="w-40 h-40 rounded-full bg-yellow-400 text-2xl font-bold shadow-lg hover:bg-yellow-300 active:scale-95 transition-transform select-none"
+                        onclick={create_dispatch_callback(state.clone(), Msg::Click)}>
+                        { locale.fill(Text::ClickTarget, &[&format(&state.click_value())]) }
+                    </button>
+                    <div class="text-gray-600 text-sm">
+                        { locale.fill(Text::ClickStats, &[
+                            &locale.number(&state.click_stats.clicks.to_string()),
+                            &format(&state.click_stats.earned),
+                        ]) }
                     </div>
+                    <button
+                        class="px-4 py-2 bg-yellow-500 text-white rounded hover:bg-yellow-600 transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+                        disabled={!state.can_afford_bulk(Purchase::ClickUpgrade, buy_amount)}
+                        onclick={buy(Purchase::ClickUpgrade)}>
+                        { click_upgrade_label }
+                    </button>
                 </div>
-            </div>
+
+                <div class="bg-gray-100 rounded-lg p-4 mb-4 flex items-center justify-between">
+                    <div>
+                        <div class="text-gray-600 text-sm">{ locale.text(Text::PrestigePoints) }</div>
+                        <div class="text-xl font-bold">{ format(&state.prestige_points) }</div>
+                        <div class="text-gray-600 text-sm">
+                            { locale.fill(Text::PrestigeMultiplier, &[&format(&state.prestige_multiplier_percent())]) }
+                        </div>
+                    </div>
+                    <button
+                        class="px-4 py-2 bg-purple-500 text-white rounded hover:bg-purple-600 transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+                        disabled={pending_prestige.is_zero()}
+                        onclick={create_dispatch_callback(state.clone(), Msg::Prestige)}>
+                        { locale.fill(Text::PrestigeFor, &[&format(&pending_prestige)]) }
+                    </button>
+                </div>
+
+                <div class="flex gap-2 mb-4 justify-end">
+                    { for BUY_AMOUNTS.iter().map(|&amount| {
+                        let class = if amount == buy_amount {
+                            "px-3 py-1 rounded bg-blue-500 text-white"
+                        } else {
+                            "px-3 py-1 rounded bg-gray-200 hover:bg-gray-300"
+                        };
+                        let onclick = {
+                            let buy_amount_handle = buy_amount_handle.clone();
+                            Callback::from(move |_| buy_amount_handle.set(amount))
+                        };
+                        html! {
+                            <button {class} {onclick}>{ buy_amount_label(amount, locale) }</button>
+                        }
+                    }) }
+                </div>
+
+                <div class="bg-gray-100 rounded-lg p-4 mb-4 flex flex-col gap-2">
+                    { for GENERATORS.iter().enumerate().map(|(tier, generator)| {
+                        let purchase = Purchase::Generator(tier);
+                        let label = bulk_label(purchase, Text::BuyCost, Text::BuyManyCost)
+                            .unwrap_or_else(|| locale.fill(Text::BuyCost, &["-"]));
+                        html! {
+                            <div class="bg-white p-3 rounded shadow flex items-center justify-between">
+                                <div>
+                                    <div class="font-bold">{ format!("{} x{}", locale.text(generator.name), locale.number(&state.generators[tier].to_string())) }</div>
+                                    <div class="text-gray-600 text-sm">{ locale.fill(Text::GeneratorOutput, &[&generator.base_output.to_string()]) }</div>
+                                </div>
+                                <button
+                                    class="px-4 py-2 bg-blue-500 text-white rounded hover:bg-blue-600 transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+                                    disabled={!state.can_afford_bulk(purchase, buy_amount)}
+                                    onclick={buy(purchase)}>
+                                    { label }
+                                </button>
+                            </div>
+                        }
+                    }) }
+                </div>
+
+                <UpgradeTree state={(**state).clone()} {locale} on_buy={on_buy_upgrade} />
+            }
 
             <div class="flex flex-col gap-2">
                 <button 
-                    class="px-4 py-3 bg-blue-500 text-white rounded hover:bg-blue-600 transition-colors"
-                    onclick={create_dispatch_callback(state.clone(), Msg::UpgradeProduction)}>
-                    { "Upgrade Production (Double)" }
+                    class="px-4 py-3 bg-blue-500 text-white rounded hover:bg-blue-600 transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+                    disabled={!state.can_afford_bulk(Purchase::ProductionUpgrade, buy_amount)}
+                    onclick={buy(Purchase::ProductionUpgrade)}>
+                    { upgrade_label }
                 </button>
                 
                 <div class="flex gap-2">
                     <button 
                         class="flex-1 px-4 py-2 bg-green-500 text-white rounded hover:bg-green-600 transition-colors"
                         onclick={create_dispatch_callback(state.clone(), Msg::Save)}>
-                        { "Save Game" }
+                        { locale.text(Text::SaveGame) }
                     </button>
                     <button 
                         class="flex-1 px-4 py-2 bg-yellow-500 text-white rounded hover:bg-yellow-600 transition-colors"
-                        onclick={on_load}>
-                        { "Load Game" }
+                        onclick={confirm(Msg::Load)}>
+                        { locale.text(Text::LoadGame) }
                     </button>
                     <button 
                         class="flex-1 px-4 py-2 bg-red-500 text-white rounded hover:bg-red-600 transition-colors"
-                        onclick={create_dispatch_callback(state.clone(), Msg::Reset)}>
-                        { "Reset Game" }
+                        onclick={confirm(Msg::Reset)}>
+                        { locale.text(Text::ResetGame) }
                     </button>
                 </div>
+                <button
+                    class="px-4 py-2 bg-gray-500 text-white rounded hover:bg-gray-600 transition-colors"
+                    onclick={on_switch_slot}>
+                    { locale.fill(Text::SwitchSlot, &[&slot_name(&state.env, props.slot)]) }
+                </button>
+            </div>
+
+            <div class="bg-gray-100 rounded-lg p-4 mt-4">
+                <div class="text-gray-600 text-sm mb-2">
+                    { locale.fill(Text::Achievements, &[&state.achievements.len().to_string(), &ACHIEVEMENTS.len().to_string()]) }
+                </div>
+                <div class="grid grid-cols-2 gap-2">
+                    { for ACHIEVEMENTS.iter().map(|achievement| {
+                        let unlocked = state.has_achievement(achievement.id);
+                        html! {
+                            <div class={classes!("p-2", "rounded", "shadow", if unlocked { "bg-white" } else { "bg-gray-200 opacity-60" })}>
+                                <div class="font-bold text-sm">{ locale.text(achievement.name) }</div>
+                                <div class="text-gray-600 text-xs">{ locale.text(achievement.description) }</div>
+                                if achievement.bonus_percent > 0 {
+                                    <div class="text-green-600 text-xs">{ locale.fill(Text::AchievementBonus, &[&achievement.bonus_percent.to_string()]) }</div>
+                                }
+                            </div>
+                        }
+                    }) }
+                </div>
+            </div>
+
+            if let Some(undo) = undo {
+                <div class="fixed bottom-4 left-4 bg-gray-800 text-white rounded-lg shadow p-3 flex items-center gap-3">
+                    <span>{ locale.text(undone_text(undo.action)) }</span>
+                    <button
+                        class="px-3 py-1 bg-blue-500 rounded hover:bg-blue-600 transition-colors"
+                        onclick={create_dispatch_callback(state.clone(), Msg::Undo)}>
+                        { locale.fill(Text::Undo, &[&undo.seconds_left(now).to_string()]) }
+                    </button>
+                    <button
+                        class="text-gray-400 hover:text-white"
+                        onclick={create_dispatch_callback(state.clone(), Msg::DismissUndo)}>
+                        { "×" }
+                    </button>
+                </div>
+            }
+
+            <div class="fixed bottom-4 right-4 flex flex-col gap-2">
+                { for state.achievement_toasts.iter().map(|id| html! {
+                    <AchievementToast
+                        key={id.clone()}
+                        id={id.clone()}
+                        {locale}
+                        on_dismiss={
+                            let state = state.clone();
+                            Callback::from(move |id| state.dispatch(Msg::DismissAchievementToast(id)))
+                        } />
+                }) }
+            </div>
+
+            <div class="bg-gray-100 rounded-lg p-4 mt-4 flex flex-col gap-2">
+                <button
+                    class="px-4 py-2 bg-gray-500 text-white rounded hover:bg-gray-600 transition-colors"
+                    onclick={on_export}>
+                    { locale.text(Text::ExportSave) }
+                </button>
+                if let Some(code) = (*export_code).clone() {
+                    <textarea
+                        class="w-full p-2 rounded border font-mono text-xs"
+                        rows="3"
+                        readonly=true
+                        value={code} />
+                }
+                <button
+                    class="px-4 py-2 bg-gray-500 text-white rounded hover:bg-gray-600 transition-colors"
+                    onclick={on_export_replay}>
+                    { locale.text(Text::ExportReplay) }
+                </button>
+                if let Some(code) = (*replay_code).clone() {
+                    <textarea
+                        class="w-full p-2 rounded border font-mono text-xs"
+                        rows="3"
+                        readonly=true
+                        value={code} />
+                }
+                <textarea
+                    class="w-full p-2 rounded border font-mono text-xs"
+                    rows="3"
+                    placeholder={locale.text(Text::ImportPlaceholder)}
+                    value={(*import_text).clone()}
+                    oninput={on_import_input} />
+                <button
+                    class="px-4 py-2 bg-gray-500 text-white rounded hover:bg-gray-600 transition-colors"
+                    onclick={on_import}>
+                    { locale.text(Text::ImportSave) }
+                </button>
+                if let Some(error) = &state.import_error {
+                    <div class="text-sm text-red-600">{ error.message(locale) }</div>
+                }
+            </div>
+
+            <SyncPanel
+                env={props.env.clone()}
+                state={(**state).clone()}
+                {locale}
+                on_save={
+                    let state = state.clone();
+                    Callback::from(move |_| state.dispatch(Msg::Save))
+                }
+                on_load={
+                    let state = state.clone();
+                    Callback::from(move |json| state.dispatch(Msg::LoadSynced(json)))
+                } />
+
+            if let Some(msg) = &*confirming {
+                <ConfirmDialog
+                    message={confirm_text(msg)}
+                    confirm_label={confirm_label(msg)}
+                    {locale}
+                    {on_confirm}
+                    {on_cancel} />
+            }
+
+            if let Some(report) = &state.offline_report {
+                <div class="fixed inset-0 bg-black bg-opacity-50 flex items-center justify-center">
+                    <div class="bg-white rounded-lg shadow p-6 max-w-sm text-center">
+                        <h2 class="text-xl font-bold mb-2">{ locale.text(Text::WelcomeBack) }</h2>
+                        <p class="mb-4">{ report.summary(notation, locale) }</p>
+                        <button
+                            class="px-4 py-2 bg-blue-500 text-white rounded hover:bg-blue-600 transition-colors"
+                            onclick={create_dispatch_callback(state.clone(), Msg::DismissOfflineReport)}>
+                            { locale.text(Text::Continue) }
+                        </button>
+                    </div>
+                </div>
+            }
+
+            <div class="mt-4 text-sm text-gray-500 flex items-center justify-center gap-2">
+                <label for="notation">{ locale.text(Text::NumberNotation) }</label>
+                <select id="notation" class="p-1 rounded border" onchange={on_notation_change}>
+                    { for NOTATIONS.iter().map(|&option| html! {
+                        <option selected={option == notation}>{ option.label(locale) }</option>
+                    }) }
+                </select>
+            </div>
+
+            <div class="mt-4 text-sm text-gray-500 flex items-center justify-center gap-2">
+                <label for="autosave">{ locale.text(Text::AutosaveEvery) }</label>
+                <select id="autosave" class="p-1 rounded border" onchange={on_autosave_change}>
+                    { for AUTOSAVE_INTERVALS.iter().map(|&seconds| html! {
+                        <option selected={seconds == state.autosave_seconds}>
+                            { locale.fill(Text::Seconds, &[&seconds.to_string()]) }
+                        </option>
+                    }) }
+                </select>
             </div>
 
             <div class="mt-4 text-sm text-gray-500 text-center">
-                { format!("Last saved: {}", state.format_last_saved()) }
+                { locale.fill(Text::LastSaved, &[&state.format_last_saved(state.env.clock.now(), locale), props.store_kind.label()]) }
+                { match state.save_status() {
+                    SaveStatus::Saved => html! {
+                        <div class="text-green-600">{ locale.text(Text::SaveStatusSaved) }</div>
+                    },
+                    SaveStatus::Pending => html! {
+                        <div class="text-yellow-600">{ locale.text(Text::SaveStatusPending) }</div>
+                    },
+                    SaveStatus::Error(error) => html! {
+                        <div class="text-red-600">{ locale.fill(Text::SaveStatusError, &[&error.message(locale)]) }</div>
+                    },
+                } }
+                if let Some(error) = &state.backup_error {
+                    <div class="text-yellow-600">{ locale.fill(Text::BackupError, &[error]) }</div>
+                }
             </div>
         </div>
     }
 }
 
-fn create_dispatch_callback(state: UseReducerHandle<State>, msg: Msg) -> Callback<MouseEvent> {
+#[derive(Clone, Copy, Default, PartialEq)]
+enum Tab {
+    #[default]
+    Play,
+    Stats,
+    Backups,
+}
+
+const TABS: &[Tab] = &[Tab::Play, Tab::Stats, Tab::Backups];
+
+impl Tab {
+    fn label(self) -> Text {
+        match self {
+            Tab::Play => Text::TabPlay,
+            Tab::Stats => Text::TabStats,
+            Tab::Backups => Text::TabBackups,
+        }
+    }
+}
+
+#[derive(Properties)]
+struct AnimatedCounterProps {
+    env: Rc<Env>,
+    state: State,
+    tick_timer: Rc<RefCell<TickTimer>>,
+    locale: Locale,
+}
+
+impl PartialEq for AnimatedCounterProps {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.env, &other.env)
+            && self.state == other.state
+            && Rc::ptr_eq(&self.tick_timer, &other.tick_timer)
+            && self.locale == other.locale
+    }
+}
+
+/// The counter as it would stand right now, redrawn every animation frame
+/// so it climbs smoothly between ticks. Only the display moves; the state
+/// still changes once per tick. Browsers stop animation frames in hidden
+/// tabs, so the loop pauses with them.
+#[function_component(AnimatedCounter)]
+fn animated_counter(props: &AnimatedCounterProps) -> Html {
+    let redraw = use_force_update();
+    use_effect_with_deps(
+        move |_| {
+            let frame = Rc::new(RefCell::new(None));
+            animate(redraw, frame.clone());
+            move || drop(frame.borrow_mut().take())
+        },
+        (),
+    );
+
+    let notation = props.state.notation;
+    let pending_ms = props.tick_timer.borrow().pending_play(props.env.clock.monotonic());
+    let shown = &props.state.counter + props.state.earned_in(pending_ms);
+    html! {
+        <>
+            <div class="text-2xl font-bold">{ notation.format(&shown, props.locale) }</div>
+            <div class="text-gray-600 text-sm">
+                { props.locale.fill(Text::Rate, &[&notation.format(&props.state.production(), props.locale)]) }
+            </div>
+        </>
+    }
+}
+
+/// Redraw on the next animation frame, and keep asking for one after that.
+fn animate(redraw: UseForceUpdateHandle, frame: Rc<RefCell<Option<AnimationFrame>>>) {
+    let next = frame.clone();
+    *frame.borrow_mut() = Some(request_animation_frame(move |_| {
+        redraw.force_update();
+        animate(redraw, next);
+    }));
+}
+
+#[derive(Properties, PartialEq)]
+struct AchievementToastProps {
+    id: String,
+    locale: Locale,
+    on_dismiss: Callback<String>,
+}
+
+/// Announces an unlocked achievement and dismisses itself after a few seconds.
+#[function_component(AchievementToast)]
+fn achievement_toast(props: &AchievementToastProps) -> Html {
+    {
+        let id = props.id.clone();
+        let on_dismiss = props.on_dismiss.clone();
+        use_effect_with_deps(
+            move |_| {
+                let timeout = Timeout::new(4000, move || on_dismiss.emit(id));
+                move || drop(timeout)
+            },
+            (),
+        );
+    }
+
+    let Some(achievement) = achievements::find(&props.id) else {
+        return html! {};
+    };
+    let on_click = {
+        let id = props.id.clone();
+        let on_dismiss = props.on_dismiss.clone();
+        Callback::from(move |_| on_dismiss.emit(id.clone()))
+    };
+    html! {
+        <div class="bg-yellow-100 border border-yellow-400 rounded shadow p-3 cursor-pointer" onclick={on_click}>
+            <div class="font-bold">{ props.locale.fill(Text::AchievementUnlocked, &[props.locale.text(achievement.name)]) }</div>
+            <div class="text-sm">{ props.locale.text(achievement.description) }</div>
+        </div>
+    }
+}
+
+fn buy_amount_label(amount: BuyAmount, locale: Locale) -> String {
+    match amount {
+        BuyAmount::One => "x1".to_string(),
+        BuyAmount::Ten => "x10".to_string(),
+        BuyAmount::Hundred => "x100".to_string(),
+        BuyAmount::Max => locale.text(Text::BuyMax).to_string(),
+    }
+}
+
+/// The question asked before `msg` throws the current game away.
+fn confirm_text(msg: &Msg) -> Text {
+    match msg {
+        Msg::Load => Text::ConfirmLoad,
+        Msg::Import(_) => Text::ConfirmImport,
+        Msg::RestoreBackup(_) => Text::ConfirmRestore,
+        _ => Text::ConfirmReset,
+    }
+}
+
+fn confirm_label(msg: &Msg) -> Text {
+    match msg {
+        Msg::Load => Text::LoadGame,
+        Msg::Import(_) => Text::ImportSave,
+        Msg::RestoreBackup(_) => Text::RestoreBackup,
+        _ => Text::ResetGame,
+    }
+}
+
+fn undone_text(action: UndoAction) -> Text {
+    match action {
+        UndoAction::Reset => Text::UndoneReset,
+        UndoAction::Load => Text::UndoneLoad,
+        UndoAction::Import => Text::UndoneImport,
+        UndoAction::RestoreBackup => Text::UndoneRestore,
+        UndoAction::Sync => Text::UndoneSync,
+    }
+}
+
+fn slot_name(env: &Env, slot: usize) -> String {
+    slots::list(env.store.as_ref())
+        .ok()
+        .and_then(|slots| slots[slot].as_ref().map(|meta| meta.name.clone()))
+        .unwrap_or_else(|| slots::default_name(slot))
+}
+
+fn create_dispatch_callback(state: UseReducerHandle<Model>, msg: Msg) -> Callback<MouseEvent> {
     Callback::from(move |_| state.dispatch(msg.clone()))
 }
@@ -0,0 +1,21 @@
+//! When the game saves itself. The front end sends `Msg::Autosave` on a
+//! timer and when the page is hidden; the reducer only writes if something
+//! worth keeping changed since the last save.
+
+use crate::error::Error;
+
+/// Autosave intervals the player can pick from, in seconds.
+pub const AUTOSAVE_INTERVALS: &[u32] = &[5, 15, 30, 60];
+
+pub const DEFAULT_AUTOSAVE_SECONDS: u32 = 5;
+
+/// What the save indicator shows.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SaveStatus {
+    /// Everything is on disk.
+    Saved,
+    /// There are changes the next autosave will write.
+    Pending,
+    /// The last save or load failed.
+    Error(Error),
+}
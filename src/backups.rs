@@ -0,0 +1,222 @@
+//! Older copies of each slot's save, so a bad write or an accidental reset
+//! can be undone. Every save may add a backup: a recent one at most once a
+//! minute, keeping the last few, and the first save of each day as a daily
+//! one. A slot's backups live together under one storage key.
+
+use serde::{Deserialize, Serialize};
+
+use crate::env::SaveStore;
+use crate::game::State;
+use crate::number::Number;
+
+/// Recent backups kept per slot.
+pub const RECENT_BACKUPS: usize = 10;
+
+/// Daily backups kept per slot.
+pub const DAILY_BACKUPS: usize = 7;
+
+/// Minimum time between recent backups, so a burst of autosaves after a
+/// mistake can't push every good copy out.
+pub const RECENT_SPACING_MS: f64 = 60_000.0;
+
+/// Most storage a slot's backups may take, in bytes of JSON. Backups share
+/// the browser's quota with the live saves, so the oldest are dropped first
+/// rather than letting them crowd a save out.
+pub const MAX_BACKUP_BYTES: usize = 256 * 1024;
+
+const DAY_MS: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupKind {
+    Recent,
+    Daily,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Backup {
+    pub kind: BackupKind,
+    pub saved_at: f64,
+    /// Counter and production as of the backup, for the restore screen.
+    pub counter: Number,
+    pub production: Number,
+    /// The save as it was written to the slot.
+    pub save: String,
+}
+
+pub fn backup_key(slot: usize) -> String {
+    format!("idle_game_backups_{}", slot)
+}
+
+/// Every backup of `slot`, newest first.
+pub fn list(store: &dyn SaveStore, slot: usize) -> Result<Vec<Backup>, String> {
+    match store.get(&backup_key(slot))? {
+        Some(json) => serde_json::from_str(&json).map_err(|e| e.to_string()),
+        None => Ok(Vec::new()),
+    }
+}
+
+pub fn find(store: &dyn SaveStore, slot: usize, saved_at: f64) -> Result<Option<Backup>, String> {
+    Ok(list(store, slot)?
+        .into_iter()
+        .find(|backup| backup.saved_at == saved_at))
+}
+
+/// Keep `json`, the save just written for `saved`, as a backup if one is due.
+pub fn record(store: &dyn SaveStore, saved: &State, json: &str) -> Result<(), String> {
+    let saved_at = saved.last_saved_at.unwrap_or(saved.last_save);
+    let mut backups = list(store, saved.slot)?;
+    let newest = |kind| backups.iter().find(|backup: &&Backup| backup.kind == kind);
+    let recent_due = newest(BackupKind::Recent)
+        .is_none_or(|last| saved_at - last.saved_at >= RECENT_SPACING_MS);
+    let daily_due = newest(BackupKind::Daily)
+        .is_none_or(|last| day(last.saved_at) < day(saved_at));
+    if !recent_due && !daily_due {
+        return Ok(());
+    }
+
+    let backup = |kind| Backup {
+        kind,
+        saved_at,
+        counter: saved.counter.clone(),
+        production: saved.production(),
+        save: json.to_string(),
+    };
+    if daily_due {
+        backups.insert(0, backup(BackupKind::Daily));
+    }
+    if recent_due {
+        backups.insert(0, backup(BackupKind::Recent));
+    }
+    let (mut recent_left, mut daily_left) = (RECENT_BACKUPS, DAILY_BACKUPS);
+    backups.retain(|backup| {
+        let left = match backup.kind {
+            BackupKind::Recent => &mut recent_left,
+            BackupKind::Daily => &mut daily_left,
+        };
+        if *left == 0 {
+            return false;
+        }
+        *left -= 1;
+        true
+    });
+    let mut json = serde_json::to_string(&backups).map_err(|e| e.to_string())?;
+    while json.len() > MAX_BACKUP_BYTES && backups.pop().is_some() {
+        json = serde_json::to_string(&backups).map_err(|e| e.to_string())?;
+    }
+    store.set(&backup_key(saved.slot), &json)
+}
+
+fn day(timestamp: f64) -> i64 {
+    (timestamp / DAY_MS).floor() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env::{Env, ManualClock};
+    use crate::error::Error;
+    use crate::game::{reducer, Msg};
+    use std::rc::Rc;
+
+    fn counters(env: &Env, kind: BackupKind) -> Vec<u32> {
+        list(env.store.as_ref(), 0)
+            .unwrap()
+            .iter()
+            .filter(|backup| backup.kind == kind)
+            .map(|backup| backup.counter.to_string().parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn recent_backups_rotate() {
+        let clock = Rc::new(ManualClock::new(0.0));
+        let env = Env::in_memory(clock.clone());
+        let mut state = State::new(0.0);
+        for counter in 0..15u32 {
+            state.counter = Number::from(counter);
+            state = reducer(&state, Msg::Save, &env);
+            clock.advance(RECENT_SPACING_MS);
+        }
+        let recent = counters(&env, BackupKind::Recent);
+        assert_eq!(recent, (5..15).rev().collect::<Vec<_>>());
+        assert_eq!(counters(&env, BackupKind::Daily), vec![0]);
+    }
+
+    #[test]
+    fn saves_in_quick_succession_keep_older_backups() {
+        let clock = Rc::new(ManualClock::new(0.0));
+        let env = Env::in_memory(clock.clone());
+        let mut state = State::new(0.0);
+        state.counter = Number::from(7u32);
+        state = reducer(&state, Msg::Save, &env);
+        for _ in 0..20 {
+            clock.advance(5_000.0);
+            state = reducer(&state, Msg::Reset, &env);
+            state = reducer(&state, Msg::Save, &env);
+        }
+        // A minute of autosaves after the reset adds one backup.
+        assert_eq!(counters(&env, BackupKind::Recent), vec![0, 7]);
+    }
+
+    #[test]
+    fn first_save_of_each_day_is_kept() {
+        let clock = Rc::new(ManualClock::new(0.0));
+        let env = Env::in_memory(clock.clone());
+        let mut state = State::new(0.0);
+        for day in 0..10u32 {
+            state.counter = Number::from(day);
+            state = reducer(&state, Msg::Save, &env);
+            clock.advance(DAY_MS / 2.0);
+            state.counter = Number::from(100 + day);
+            state = reducer(&state, Msg::Save, &env);
+            clock.advance(DAY_MS / 2.0);
+        }
+        assert_eq!(counters(&env, BackupKind::Daily), (3..10).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn backups_stay_within_their_budget() {
+        let clock = Rc::new(ManualClock::new(0.0));
+        let env = Env::in_memory(clock.clone());
+        let mut state = State::new(0.0);
+        // Pad the save to about 60 KB.
+        state.upgrades = vec!["x".repeat(60_000)];
+        for counter in 0..10u32 {
+            state.counter = Number::from(counter);
+            state = reducer(&state, Msg::Save, &env);
+            clock.advance(RECENT_SPACING_MS);
+        }
+        let json = env.store.get(&backup_key(0)).unwrap().unwrap();
+        assert!(json.len() <= MAX_BACKUP_BYTES);
+        // The newest backups are the ones kept.
+        assert_eq!(counters(&env, BackupKind::Recent), vec![9, 8, 7]);
+        assert_eq!(state.backup_error, None);
+    }
+
+    #[test]
+    fn restoring_goes_through_loading() {
+        let clock = Rc::new(ManualClock::new(0.0));
+        let env = Env::in_memory(clock.clone());
+        let state = State {
+            counter: Number::from(500u32),
+            ..State::new(0.0)
+        };
+        let saved = reducer(&state, Msg::Save, &env);
+        clock.advance(RECENT_SPACING_MS);
+        let reset = reducer(&reducer(&saved, Msg::Reset, &env), Msg::Save, &env);
+
+        let backup = &list(env.store.as_ref(), 0).unwrap()[1];
+        assert_eq!(backup.counter, Number::from(500u32));
+        assert_eq!(backup.production, Number::from(1u32));
+        let restored = reducer(&reset, Msg::RestoreBackup(backup.saved_at), &env);
+        // Offline progress is credited as for any load.
+        assert_eq!(restored.counter, Number::from(530u32));
+        assert_eq!(restored.stats.loads, 1);
+        assert!(restored.dirty);
+
+        let missing = reducer(&reset, Msg::RestoreBackup(12.0), &env);
+        assert_eq!(missing.storage_error, Some(Error::BackupNotFound));
+        assert_eq!(missing.counter, reset.counter);
+    }
+}
@@ -0,0 +1,81 @@
+use std::rc::Rc;
+
+use idle_game::backups::{self, BackupKind};
+use idle_game::env::Env;
+use idle_game::locale::{Locale, Text};
+use idle_game::notation::Notation;
+use wasm_bindgen::JsValue;
+use yew::prelude::*;
+
+#[derive(Properties)]
+pub struct BackupsTabProps {
+    pub env: Rc<Env>,
+    pub slot: usize,
+    pub notation: Notation,
+    pub locale: Locale,
+    /// Called with the `saved_at` of the backup to restore.
+    pub on_restore: Callback<f64>,
+}
+
+impl PartialEq for BackupsTabProps {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.env, &other.env)
+            && self.slot == other.slot
+            && self.notation == other.notation
+            && self.locale == other.locale
+            && self.on_restore == other.on_restore
+    }
+}
+
+/// The slot's backups, newest first, each with a button to restore it.
+#[function_component(BackupsTab)]
+pub fn backups_tab(props: &BackupsTabProps) -> Html {
+    let locale = props.locale;
+    let backups = match backups::list(props.env.store.as_ref(), props.slot) {
+        Ok(backups) => backups,
+        Err(e) => {
+            return html! {
+                <div class="bg-gray-100 rounded-lg p-4 mb-4 text-red-600">
+                    { locale.fill(Text::SaveStatusError, &[&e]) }
+                </div>
+            }
+        }
+    };
+    let format = |num| props.notation.format(num, locale);
+
+    html! {
+        <div class="bg-gray-100 rounded-lg p-4 mb-4 flex flex-col gap-2">
+            if backups.is_empty() {
+                <div class="text-gray-600">{ locale.text(Text::NoBackups) }</div>
+            }
+            { for backups.iter().map(|backup| {
+                let kind = match backup.kind {
+                    BackupKind::Recent => locale.text(Text::BackupRecent),
+                    BackupKind::Daily => locale.text(Text::BackupDaily),
+                };
+                let saved_at = js_sys::Date::new(&JsValue::from_f64(backup.saved_at))
+                    .to_locale_string(locale.tag(), &JsValue::UNDEFINED);
+                let on_click = {
+                    let on_restore = props.on_restore.clone();
+                    let saved_at = backup.saved_at;
+                    Callback::from(move |_| on_restore.emit(saved_at))
+                };
+                html! {
+                    <div class="bg-white p-3 rounded shadow flex items-center justify-between">
+                        <div>
+                            <div class="font-bold">{ format!("{} - {}", kind, String::from(saved_at)) }</div>
+                            <div class="text-gray-600 text-sm">
+                                { locale.fill(Text::BackupSummary, &[&format(&backup.counter), &format(&backup.production)]) }
+                            </div>
+                        </div>
+                        <button
+                            class="px-4 py-2 bg-blue-500 text-white rounded hover:bg-blue-600 transition-colors"
+                            onclick={on_click}>
+                            { locale.text(Text::RestoreBackup) }
+                        </button>
+                    </div>
+                }
+            }) }
+        </div>
+    }
+}
@@ -0,0 +1,392 @@
+//! Fixed-size numbers for very large values: an `f64` mantissa in `[1, 10)`
+//! times a power of ten held in an `i64`. Every operation costs the same
+//! however big the value gets, at the price of about 15 significant digits.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{AddAssign, SubAssign};
+use std::str::FromStr;
+
+use num_traits::{One, Zero};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::number::binops;
+
+/// Relative difference below which two values count as equal, so float
+/// rounding can't make an exactly affordable price unaffordable.
+const TOLERANCE: f64 = 1e-12;
+
+/// Powers of ten apart beyond which the smaller operand can't change a sum.
+const SIGNIFICANT: i64 = 17;
+
+/// Deliberately not `Copy`, like `Decimal`, so code written against
+/// `Number` borrows and clones the same way whichever backend it builds with.
+#[derive(Clone, Default)]
+pub struct BigFloat {
+    /// In `[1, 10)`, or zero for zero.
+    mantissa: f64,
+    exponent: i64,
+}
+
+impl BigFloat {
+    fn new(mantissa: f64, exponent: i64) -> BigFloat {
+        assert!(mantissa.is_finite(), "BigFloat mantissa {} is not finite", mantissa);
+        if mantissa <= 0.0 {
+            return BigFloat::zero();
+        }
+        let shift = mantissa.log10().floor();
+        let mut mantissa = mantissa / 10f64.powi(shift as i32);
+        let mut exponent = exponent + shift as i64;
+        // `log10` can be off by one ulp either way.
+        if mantissa >= 10.0 {
+            mantissa /= 10.0;
+            exponent += 1;
+        } else if mantissa < 1.0 {
+            mantissa *= 10.0;
+            exponent -= 1;
+        }
+        BigFloat { mantissa, exponent }
+    }
+
+    pub fn from_f64(value: f64) -> BigFloat {
+        BigFloat::new(value, 0)
+    }
+
+    pub fn from_ratio(num: u64, den: u64) -> BigFloat {
+        BigFloat::from_f64(num as f64 / den as f64)
+    }
+
+    pub fn floor(&self) -> BigFloat {
+        if self.exponent < 0 {
+            return BigFloat::zero();
+        }
+        if self.exponent >= SIGNIFICANT {
+            return self.clone();
+        }
+        let value = self.mantissa * 10f64.powi(self.exponent as i32);
+        BigFloat::from_f64((value * (1.0 + TOLERANCE)).floor())
+    }
+
+    pub fn sqrt(&self) -> BigFloat {
+        let odd = self.exponent.rem_euclid(2);
+        BigFloat::new(
+            (self.mantissa * 10f64.powi(odd as i32)).sqrt(),
+            (self.exponent - odd) / 2,
+        )
+    }
+
+    pub fn log10(&self) -> f64 {
+        self.exponent as f64 + self.mantissa.log10()
+    }
+
+    pub fn checked_sub(&self, other: &BigFloat) -> Option<BigFloat> {
+        if self < other {
+            return None;
+        }
+        if other.is_zero() {
+            return Some(self.clone());
+        }
+        let gap = self.exponent - other.exponent;
+        if gap > SIGNIFICANT {
+            return Some(self.clone());
+        }
+        let mantissa = self.mantissa - other.mantissa / 10f64.powi(gap as i32);
+        // What is left of equal operands is rounding noise.
+        if mantissa <= self.mantissa * TOLERANCE {
+            return Some(BigFloat::zero());
+        }
+        Some(BigFloat::new(mantissa, self.exponent))
+    }
+
+    /// `self^exponent` by repeated squaring.
+    pub fn pow(&self, mut exponent: u32) -> BigFloat {
+        let mut base = self.clone();
+        let mut result = BigFloat::one();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result * &base;
+            }
+            exponent >>= 1;
+            base = &base * &base;
+        }
+        result
+    }
+
+    /// Significant decimal digits without trailing zeros, and the power of
+    /// ten the first one stands for (`12.5` is `("125", 1)`).
+    pub fn digits(&self) -> (String, i64) {
+        if self.is_zero() {
+            return ("0".to_string(), 0);
+        }
+        // Fifteen digits are all an f64 reliably holds.
+        let printed = format!("{:.14e}", self.mantissa);
+        let (mantissa, shift) = printed.split_once('e').expect("exponent format");
+        let digits = mantissa.replace('.', "");
+        let shift: i64 = shift.parse().expect("exponent format");
+        (digits.trim_end_matches('0').to_string(), self.exponent + shift)
+    }
+}
+
+impl Zero for BigFloat {
+    fn zero() -> BigFloat {
+        BigFloat::default()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.mantissa == 0.0
+    }
+}
+
+impl One for BigFloat {
+    fn one() -> BigFloat {
+        BigFloat::from_f64(1.0)
+    }
+}
+
+impl From<u32> for BigFloat {
+    fn from(value: u32) -> BigFloat {
+        BigFloat::from_f64(value.into())
+    }
+}
+
+impl From<u64> for BigFloat {
+    fn from(value: u64) -> BigFloat {
+        BigFloat::from_f64(value as f64)
+    }
+}
+
+fn add(a: &BigFloat, b: &BigFloat) -> BigFloat {
+    let (big, small) = if a.exponent >= b.exponent { (a, b) } else { (b, a) };
+    if small.is_zero() || big.exponent - small.exponent > SIGNIFICANT {
+        return big.clone();
+    }
+    let gap = big.exponent - small.exponent;
+    BigFloat::new(big.mantissa + small.mantissa / 10f64.powi(gap as i32), big.exponent)
+}
+
+fn sub(a: &BigFloat, b: &BigFloat) -> BigFloat {
+    a.checked_sub(b).expect("BigFloat subtraction underflowed")
+}
+
+fn mul(a: &BigFloat, b: &BigFloat) -> BigFloat {
+    if a.is_zero() || b.is_zero() {
+        return BigFloat::zero();
+    }
+    BigFloat::new(a.mantissa * b.mantissa, a.exponent + b.exponent)
+}
+
+fn div(a: &BigFloat, b: &BigFloat) -> BigFloat {
+    assert!(!b.is_zero(), "BigFloat division by zero");
+    if a.is_zero() {
+        return BigFloat::zero();
+    }
+    BigFloat::new(a.mantissa / b.mantissa, a.exponent - b.exponent)
+}
+
+binops!(BigFloat; Add add add, Sub sub sub, Mul mul mul, Div div div);
+
+impl AddAssign<&BigFloat> for BigFloat {
+    fn add_assign(&mut self, rhs: &BigFloat) {
+        *self = add(self, rhs);
+    }
+}
+
+impl AddAssign<BigFloat> for BigFloat {
+    fn add_assign(&mut self, rhs: BigFloat) {
+        *self = add(self, &rhs);
+    }
+}
+
+impl SubAssign<&BigFloat> for BigFloat {
+    fn sub_assign(&mut self, rhs: &BigFloat) {
+        *self = sub(self, rhs);
+    }
+}
+
+impl Sum for BigFloat {
+    fn sum<I: Iterator<Item = BigFloat>>(iter: I) -> BigFloat {
+        iter.fold(BigFloat::zero(), |sum, item| sum + item)
+    }
+}
+
+/// Equal within `TOLERANCE`, so not transitive across long chains of
+/// near-equal values; game rules only ever compare two at a time.
+impl PartialEq for BigFloat {
+    fn eq(&self, other: &BigFloat) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for BigFloat {
+    fn partial_cmp(&self, other: &BigFloat) -> Option<Ordering> {
+        let ordering = match (self.is_zero(), other.is_zero()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => match self.exponent - other.exponent {
+                gap if gap > 1 => Ordering::Greater,
+                gap if gap < -1 => Ordering::Less,
+                gap => {
+                    let mantissa = self.mantissa * 10f64.powi(gap as i32);
+                    let scale = mantissa.max(other.mantissa);
+                    if (mantissa - other.mantissa).abs() <= scale * TOLERANCE {
+                        Ordering::Equal
+                    } else {
+                        mantissa.total_cmp(&other.mantissa)
+                    }
+                }
+            },
+        };
+        Some(ordering)
+    }
+}
+
+/// Plain decimals like `1234.5` for moderate values, `1.2345e1000` beyond.
+impl fmt::Display for BigFloat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (digits, exponent) = self.digits();
+        let (first, rest) = digits.split_at(1);
+        match exponent {
+            0..=20 => {
+                let whole = exponent as usize + 1;
+                let padded = format!("{:0<whole$}", digits);
+                let (int, frac) = padded.split_at(whole);
+                match frac {
+                    "" => f.write_str(int),
+                    frac => write!(f, "{}.{}", int, frac),
+                }
+            }
+            -6..=-1 => {
+                let zeros = "0".repeat((-exponent - 1) as usize);
+                write!(f, "0.{}{}", zeros, digits)
+            }
+            _ if rest.is_empty() => write!(f, "{}e{}", first, exponent),
+            _ => write!(f, "{}.{}e{}", first, rest, exponent),
+        }
+    }
+}
+
+impl fmt::Debug for BigFloat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Parses plain decimals of any length and `1.5e1000` style exponents.
+impl FromStr for BigFloat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<BigFloat, String> {
+        let invalid = || format!("invalid number {:?}", s);
+        let (mantissa, exponent) = match s.split_once(['e', 'E']) {
+            Some((mantissa, exponent)) => (mantissa, exponent.parse::<i64>().map_err(|_| invalid())?),
+            None => (s, 0),
+        };
+        let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let all_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if int.is_empty() || !all_digits(int) || !all_digits(frac) {
+            return Err(invalid());
+        }
+        let digits = format!("{}{}", int, frac);
+        let Some(first) = digits.find(|d| d != '0') else {
+            return Ok(BigFloat::zero());
+        };
+        let significant = &digits[first..digits.len().min(first + SIGNIFICANT as usize)];
+        let value: f64 = significant.parse().map_err(|_| invalid())?;
+        let power = int.len() as i64 - 1 - first as i64 - (significant.len() as i64 - 1);
+        Ok(BigFloat::new(value, exponent + power))
+    }
+}
+
+impl Serialize for BigFloat {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_string().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BigFloat {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<BigFloat, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn f(s: &str) -> BigFloat {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_and_prints() {
+        for s in ["0", "7", "0.5", "1.15", "1234.5", "1.2345e1000", "5e-30"] {
+            assert_eq!(f(s).to_string(), s);
+        }
+        assert_eq!(f("123456789012345678901234567890").to_string(), "1.23456789012346e29");
+        assert_eq!(f("0012.50").to_string(), "12.5");
+        assert_eq!(f("1.5E3").to_string(), "1500");
+        assert_eq!(f("0.000").to_string(), "0");
+        for bad in ["", "-1", "1.2.3", ".5", "1e", "e5", "abc"] {
+            assert!(bad.parse::<BigFloat>().is_err(), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(f("1.5") + f("2.25"), f("3.75"));
+        assert_eq!(f("5") - f("0.5"), f("4.5"));
+        assert_eq!(f("1.15") * f("10"), f("11.5"));
+        assert_eq!(f("1") / f("4"), f("0.25"));
+        assert_eq!(f("1e1000") * f("1e1000"), f("1e2000"));
+        assert_eq!(f("3e1000") / f("1.5e10"), f("2e990"));
+        assert_eq!(f("1e1000") + f("1"), f("1e1000"));
+        assert_eq!(f("0.1") + f("0.2"), f("0.3"));
+        assert_eq!(BigFloat::from_ratio(115, 100), f("1.15"));
+        assert!((f("1.3") * f("1.05") - f("1.365")).is_zero());
+        assert_eq!(f("0.5").checked_sub(&f("1")), None);
+        assert_eq!([f("1e5"), f("2e5")].into_iter().sum::<BigFloat>(), f("3e5"));
+    }
+
+    #[test]
+    fn comparison() {
+        assert!(f("1e1000") > f("9.99e999"));
+        assert!(f("1e-5") > BigFloat::zero());
+        assert!(f("2") < f("10"));
+        assert!(f("9.5") < f("10"));
+        assert_eq!(f("0.3"), f("0.1") + f("0.2"));
+        assert_ne!(f("1"), f("1.000001"));
+    }
+
+    #[test]
+    fn powers_roots_and_logs() {
+        assert_eq!(f("2").pow(10), f("1024"));
+        assert_eq!(f("1.15").pow(3), f("1.520875"));
+        assert_eq!(f("10").pow(4000), f("1e4000"));
+        assert_eq!(f("1e1000").sqrt(), f("1e500"));
+        assert_eq!(f("1e1001").sqrt(), f("3.16227766016838e500"));
+        assert_eq!(f("9.5").sqrt().floor(), f("3"));
+        assert_eq!(f("7.9").floor(), f("7"));
+        assert_eq!(f("0.9").floor(), BigFloat::zero());
+        assert!((f("1e1000").log10() - 1000.0).abs() < 1e-9);
+        assert!((f("2e50").log10() - 50.30103).abs() < 1e-5);
+    }
+
+    #[test]
+    fn digits() {
+        assert_eq!(f("12.5").digits(), ("125".to_string(), 1));
+        assert_eq!(f("9.999999999999999").digits(), ("1".to_string(), 1));
+        assert_eq!(f("0.05").digits(), ("5".to_string(), -2));
+        assert_eq!(f("4.2e12345").digits(), ("42".to_string(), 12345));
+    }
+
+    #[test]
+    fn serializes_as_a_string() {
+        assert_eq!(serde_json::to_string(&f("1.5e300")).unwrap(), r#""1.5e300""#);
+        let parsed: BigFloat = serde_json::from_str(r#""2.5""#).unwrap();
+        assert_eq!(parsed, f("2.5"));
+    }
+}
@@ -0,0 +1,90 @@
+//! Earning by hand: what a click is worth, how its power is upgraded, and a
+//! cap on click rate so autoclickers can't outpace a person.
+
+use std::collections::VecDeque;
+
+use num_traits::Zero;
+use serde::{Deserialize, Serialize};
+
+use crate::cost::CostCurve;
+use crate::number::Number;
+
+/// Most clicks credited in any one second; faster clicks are ignored.
+pub const MAX_CLICKS_PER_SECOND: usize = 20;
+
+/// Counter earned by a click before any click-power upgrades.
+pub const BASE_CLICK_VALUE: u32 = 1;
+
+/// Price of each click-power upgrade; every level doubles the click value.
+pub fn click_upgrade_cost() -> CostCurve {
+    CostCurve::Geometric {
+        base: Number::from(25u32),
+        ratio: Number::from(4u32),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ClickStats {
+    /// Clicks that earned something.
+    pub clicks: u64,
+    /// Everything earned by clicking.
+    pub earned: Number,
+    /// Clicks ignored for coming faster than `MAX_CLICKS_PER_SECOND`.
+    pub rejected: u64,
+}
+
+impl Default for ClickStats {
+    fn default() -> Self {
+        Self {
+            clicks: 0,
+            earned: Number::zero(),
+            rejected: 0,
+        }
+    }
+}
+
+/// Sliding one-second window over recently credited clicks.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ClickLimiter {
+    /// Monotonic times in ms, oldest first.
+    recent: VecDeque<f64>,
+}
+
+impl ClickLimiter {
+    /// Whether a click at `now` is within the cap, recording it if so.
+    pub fn allow(&mut self, now: f64) -> bool {
+        while self.recent.front().is_some_and(|&at| now - at >= 1000.0) {
+            self.recent.pop_front();
+        }
+        if self.recent.len() >= MAX_CLICKS_PER_SECOND {
+            return false;
+        }
+        self.recent.push_back(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caps_clicks_per_second() {
+        let mut limiter = ClickLimiter::default();
+        for i in 0..MAX_CLICKS_PER_SECOND {
+            assert!(limiter.allow(i as f64));
+        }
+        assert!(!limiter.allow(500.0));
+        // The first click leaves the window a second after it was made.
+        assert!(!limiter.allow(999.0));
+        assert!(limiter.allow(1000.0));
+        assert!(!limiter.allow(1000.5));
+    }
+
+    #[test]
+    fn human_clicking_is_never_capped() {
+        let mut limiter = ClickLimiter::default();
+        // Ten clicks a second for a minute.
+        assert!((0..600).all(|i| limiter.allow(i as f64 * 100.0)));
+    }
+}
@@ -0,0 +1,43 @@
+use idle_game::locale::{Locale, Text};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct ConfirmDialogProps {
+    /// What will happen, phrased as a question.
+    pub message: Text,
+    pub confirm_label: Text,
+    pub locale: Locale,
+    pub on_confirm: Callback<()>,
+    pub on_cancel: Callback<()>,
+}
+
+/// Asks before doing something that throws progress away. Clicking outside
+/// the dialog cancels.
+#[function_component(ConfirmDialog)]
+pub fn confirm_dialog(props: &ConfirmDialogProps) -> Html {
+    let locale = props.locale;
+    let on_confirm = props.on_confirm.reform(|_: MouseEvent| ());
+    let on_cancel = props.on_cancel.reform(|_: MouseEvent| ());
+    let stop = Callback::from(|e: MouseEvent| e.stop_propagation());
+
+    html! {
+        <div class="fixed inset-0 bg-black bg-opacity-50 flex items-center justify-center" onclick={on_cancel.clone()}>
+            <div class="bg-white rounded-lg shadow p-6 max-w-sm text-center" onclick={stop}>
+                <h2 class="text-xl font-bold mb-2">{ locale.text(Text::ConfirmTitle) }</h2>
+                <p class="mb-4">{ locale.text(props.message) }</p>
+                <div class="flex gap-2">
+                    <button
+                        class="flex-1 px-4 py-2 bg-gray-500 text-white rounded hover:bg-gray-600 transition-colors"
+                        onclick={on_cancel}>
+                        { locale.text(Text::Cancel) }
+                    </button>
+                    <button
+                        class="flex-1 px-4 py-2 bg-red-500 text-white rounded hover:bg-red-600 transition-colors"
+                        onclick={on_confirm}>
+                        { locale.text(props.confirm_label) }
+                    </button>
+                </div>
+            </div>
+        </div>
+    }
+}
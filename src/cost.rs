@@ -0,0 +1,250 @@
+use num_traits::{One, Zero};
+
+use crate::number::Number;
+
+/// How the price of successive purchases grows with the number already bought.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CostCurve {
+    /// `base * ratio^level`.
+    Geometric { base: Number, ratio: Number },
+    /// `base * (level + 1)^exponent`.
+    Polynomial { base: Number, exponent: u32 },
+    /// Explicit price per level; levels past the end of the table can't be bought.
+    Table(Vec<Number>),
+}
+
+impl CostCurve {
+    /// Price of the purchase that takes the owner from `level` to `level + 1`.
+    pub fn price(&self, level: u32) -> Option<Number> {
+        match self {
+            CostCurve::Geometric { base, ratio } => Some(base * ratio.pow(level)),
+            CostCurve::Polynomial { base, exponent } => {
+                Some(base * Number::from(level + 1).pow(*exponent))
+            }
+            CostCurve::Table(prices) => prices.get(level as usize).cloned(),
+        }
+    }
+
+    /// Combined price of `quantity` purchases starting at `level`, or `None`
+    /// if any of them can't be bought. Geometric curves sum the series in
+    /// closed form, `price(level) * (ratio^quantity - 1) / (ratio - 1)`, and
+    /// polynomial ones as a difference of power sums, so buying thousands of
+    /// levels costs no more than buying one.
+    pub fn total_price(&self, level: u32, quantity: u32) -> Option<Number> {
+        let end = level.checked_add(quantity)?;
+        match self {
+            CostCurve::Geometric { base, ratio } if *ratio > Number::one() => {
+                let first = base * ratio.pow(level);
+                let growth = ratio.pow(quantity) - Number::one();
+                Some(first * growth / (ratio - Number::one()))
+            }
+            // Levels `level..end` cost `base * m^exponent` for `m` in
+            // `level + 1..=end`.
+            CostCurve::Polynomial { base, exponent } => {
+                let sum = power_sum(end, *exponent)
+                    .checked_sub(&power_sum(level, *exponent))
+                    .unwrap_or_else(Number::zero);
+                Some(base * sum)
+            }
+            _ => (level..end).map(|level| self.price(level)).sum(),
+        }
+    }
+
+    /// The most purchases starting at `level` that `budget` covers.
+    pub fn max_affordable(&self, level: u32, budget: &Number) -> u32 {
+        let Some(first) = self.price(level) else {
+            return 0;
+        };
+        if *budget < first {
+            return 0;
+        }
+        let affordable = |quantity| {
+            self.total_price(level, quantity)
+                .is_some_and(|total| total <= *budget)
+        };
+        match self {
+            // Solve `total_price(level, n) <= budget` for `n`, then correct
+            // the estimate for rounding in the logarithms.
+            CostCurve::Geometric { ratio, .. } if *ratio > Number::one() => {
+                let series = budget * (ratio - Number::one()) / &first + Number::one();
+                let mut quantity =
+                    (series.log10() / ratio.log10()).floor().clamp(1.0, MAX_QUANTITY as f64) as u32;
+                while quantity > 1 && !affordable(quantity) {
+                    quantity -= 1;
+                }
+                while quantity < MAX_QUANTITY && affordable(quantity + 1) {
+                    quantity += 1;
+                }
+                quantity
+            }
+            // Other curves have no inverse to solve, so double the quantity
+            // until the budget runs out, then halve the gap between the last
+            // quantity it covered and the first it didn't.
+            _ => {
+                let mut covered = 1;
+                let mut uncovered = 2;
+                while affordable(uncovered) {
+                    if uncovered == MAX_QUANTITY {
+                        return MAX_QUANTITY;
+                    }
+                    covered = uncovered;
+                    uncovered = (uncovered * 2).min(MAX_QUANTITY);
+                }
+                while uncovered - covered > 1 {
+                    let middle = covered + (uncovered - covered) / 2;
+                    if affordable(middle) {
+                        covered = middle;
+                    } else {
+                        uncovered = middle;
+                    }
+                }
+                covered
+            }
+        }
+    }
+}
+
+/// `0^k + 1^k + ... + n^k`, counting `0^0` as one. Summed as
+/// `S(k, j) * (n + 1)(n)...(n + 1 - j) / (j + 1)` over `j`, where `S` are the
+/// Stirling numbers of the second kind, so it takes `k^2` steps however big
+/// `n` is and every term is positive.
+fn power_sum(n: u32, k: u32) -> Number {
+    // Row `k` of the Stirling numbers, built up from row 0.
+    let mut stirling = vec![Number::one()];
+    for row in 1..=k as usize {
+        let mut next = vec![Number::zero(); row + 1];
+        for j in 1..=row {
+            let kept = stirling.get(j).map_or_else(Number::zero, |s| Number::from(j as u32) * s);
+            next[j] = kept + &stirling[j - 1];
+        }
+        stirling = next;
+    }
+
+    let mut sum = Number::zero();
+    let mut falling = Number::one();
+    for (j, s) in (0..).zip(&stirling) {
+        // The product reaches zero once `j` passes `n`.
+        let Some(factor) = (u64::from(n) + 1).checked_sub(j) else {
+            break;
+        };
+        falling = falling * Number::from(factor);
+        sum += s * &falling / Number::from(j + 1);
+    }
+    sum
+}
+
+/// Upper bound on a single bulk purchase, so a huge budget can't ask for a
+/// price with an absurd power in it.
+const MAX_QUANTITY: u32 = 1_000_000;
+
+pub fn production_upgrade_cost() -> CostCurve {
+    CostCurve::Geometric {
+        base: Number::from(10u32),
+        ratio: Number::from_ratio(5, 2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::number::d;
+
+    #[test]
+    fn cost_curves() {
+        let geometric = CostCurve::Geometric { base: d("10"), ratio: d("1.5") };
+        assert_eq!(geometric.price(0), Some(d("10")));
+        assert_eq!(geometric.price(2), Some(d("22.5")));
+
+        let polynomial = CostCurve::Polynomial { base: d("5"), exponent: 2 };
+        assert_eq!(polynomial.price(0), Some(d("5")));
+        assert_eq!(polynomial.price(3), Some(d("80")));
+
+        let table = CostCurve::Table(vec![d("1"), d("7.5")]);
+        assert_eq!(table.price(1), Some(d("7.5")));
+        assert_eq!(table.price(2), None);
+    }
+
+    fn brute_force_total(curve: &CostCurve, level: u32, quantity: u32) -> Option<Number> {
+        let mut total = d("0");
+        for level in level..level + quantity {
+            total += curve.price(level)?;
+        }
+        Some(total)
+    }
+
+    fn brute_force_max(curve: &CostCurve, level: u32, budget: &Number) -> u32 {
+        let mut spent: Number = d("0");
+        let mut quantity = 0;
+        while let Some(price) = curve.price(level + quantity) {
+            spent += price;
+            if spent > *budget {
+                break;
+            }
+            quantity += 1;
+        }
+        quantity
+    }
+
+    #[test]
+    fn totals_match_buying_one_at_a_time() {
+        let curves = [
+            production_upgrade_cost(),
+            CostCurve::Geometric { base: d("10"), ratio: d("1.15") },
+            CostCurve::Polynomial { base: d("5"), exponent: 2 },
+            CostCurve::Polynomial { base: d("2"), exponent: 0 },
+            CostCurve::Polynomial { base: d("0.5"), exponent: 7 },
+            CostCurve::Table(vec![d("1"), d("7.5"), d("20")]),
+        ];
+        for curve in &curves {
+            for level in [0, 3, 17] {
+                for quantity in [0, 1, 2, 10, 100] {
+                    let (Some(closed), Some(looped)) = (
+                        curve.total_price(level, quantity),
+                        brute_force_total(curve, level, quantity),
+                    ) else {
+                        assert_eq!(curve.total_price(level, quantity), None);
+                        continue;
+                    };
+                    // The series only differs from the sum in the last digits.
+                    let error = closed.clone().checked_sub(&looped).or_else(|| looped.checked_sub(&closed));
+                    assert!(error.unwrap() <= &looped / d::<Number>("1e12"), "{:?} {} {}", curve, level, quantity);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn max_affordable_matches_brute_force() {
+        let curves = [
+            production_upgrade_cost(),
+            CostCurve::Geometric { base: d("10"), ratio: d("1.15") },
+            CostCurve::Polynomial { base: d("5"), exponent: 2 },
+            CostCurve::Table(vec![d("1"), d("7.5"), d("20")]),
+        ];
+        for curve in &curves {
+            for level in [0, 1, 5] {
+                for budget in ["0", "1", "9.99", "10", "34", "35", "1000", "123456.7", "1e9"] {
+                    let budget = d(budget);
+                    assert_eq!(
+                        curve.max_affordable(level, &budget),
+                        brute_force_max(curve, level, &budget),
+                        "{:?} {} {}",
+                        curve,
+                        level,
+                        budget
+                    );
+                }
+            }
+        }
+        // Far more levels than anyone would buy one at a time.
+        let cursor = CostCurve::Geometric { base: d("10"), ratio: d("1.15") };
+        let quantity = cursor.max_affordable(0, &d("1e100"));
+        assert!(cursor.total_price(0, quantity).unwrap() <= d("1e100"));
+        assert!(cursor.total_price(0, quantity + 1).unwrap() > d("1e100"));
+        let polynomial = CostCurve::Polynomial { base: d("5"), exponent: 2 };
+        let quantity = polynomial.max_affordable(3, &d("1e15"));
+        assert!(quantity > 80_000);
+        assert!(polynomial.total_price(3, quantity).unwrap() <= d("1e15"));
+        assert!(polynomial.total_price(3, quantity + 1).unwrap() > d("1e15"));
+    }
+}
@@ -0,0 +1,320 @@
+//! Non-negative decimal numbers of unbounded size with a fixed number of
+//! fractional digits, so rates like 1.15x cost growth or 0.5/s production
+//! don't have to be rounded to whole numbers.
+
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{AddAssign, SubAssign};
+use std::str::FromStr;
+
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::number::binops;
+
+/// Fractional digits kept. Products and quotients round half up to this.
+pub const SCALE: u32 = 18;
+
+/// `10^SCALE`, the raw value of one.
+const ONE: u64 = 1_000_000_000_000_000_000;
+
+/// Largest power of ten a parsed exponent may ask for, so a corrupt save
+/// can't make us allocate a number with billions of digits.
+const MAX_EXPONENT: i64 = 1_000_000;
+
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Decimal {
+    /// The value times `10^SCALE`.
+    raw: BigUint,
+}
+
+impl Decimal {
+    /// `num / den`, rounded to `SCALE` digits.
+    pub fn from_ratio(num: u64, den: u64) -> Decimal {
+        Decimal::from(num) / Decimal::from(den)
+    }
+
+    /// The whole part, dropping any fraction.
+    pub fn floor(&self) -> Decimal {
+        Decimal {
+            raw: &self.raw / ONE * ONE,
+        }
+    }
+
+    /// Square root, rounded down to `SCALE` digits.
+    pub fn sqrt(&self) -> Decimal {
+        Decimal {
+            raw: (&self.raw * ONE).sqrt(),
+        }
+    }
+
+    pub fn log10(&self) -> f64 {
+        let (digits, exponent) = self.digits();
+        let leading = &digits[..digits.len().min(17)];
+        let mantissa: f64 = leading.parse().unwrap_or(0.0);
+        exponent as f64 + mantissa.log10() - (leading.len() as f64 - 1.0)
+    }
+
+    pub fn checked_sub(&self, other: &Decimal) -> Option<Decimal> {
+        (self.raw >= other.raw).then(|| Decimal {
+            raw: &self.raw - &other.raw,
+        })
+    }
+
+    /// `self^exponent` by repeated squaring, rounding after each product.
+    pub fn pow(&self, mut exponent: u32) -> Decimal {
+        let mut base = self.clone();
+        let mut result = Decimal::one();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    /// Significant decimal digits without trailing zeros, and the power of
+    /// ten the first one stands for (`12.5` is `("125", 1)`).
+    pub fn digits(&self) -> (String, i64) {
+        if self.raw.is_zero() {
+            return ("0".to_string(), 0);
+        }
+        let raw = self.raw.to_string();
+        let exponent = raw.len() as i64 - 1 - SCALE as i64;
+        (raw.trim_end_matches('0').to_string(), exponent)
+    }
+}
+
+impl Zero for Decimal {
+    fn zero() -> Decimal {
+        Decimal::default()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.raw.is_zero()
+    }
+}
+
+impl One for Decimal {
+    fn one() -> Decimal {
+        Decimal::from(1u32)
+    }
+}
+
+impl From<u32> for Decimal {
+    fn from(value: u32) -> Decimal {
+        Decimal::from(BigUint::from(value))
+    }
+}
+
+impl From<u64> for Decimal {
+    fn from(value: u64) -> Decimal {
+        Decimal::from(BigUint::from(value))
+    }
+}
+
+impl From<BigUint> for Decimal {
+    fn from(value: BigUint) -> Decimal {
+        Decimal { raw: value * ONE }
+    }
+}
+
+fn add(a: &Decimal, b: &Decimal) -> Decimal {
+    Decimal { raw: &a.raw + &b.raw }
+}
+
+fn sub(a: &Decimal, b: &Decimal) -> Decimal {
+    a.checked_sub(b).expect("Decimal subtraction underflowed")
+}
+
+fn mul(a: &Decimal, b: &Decimal) -> Decimal {
+    Decimal {
+        raw: (&a.raw * &b.raw + ONE / 2) / ONE,
+    }
+}
+
+fn div(a: &Decimal, b: &Decimal) -> Decimal {
+    assert!(!b.is_zero(), "Decimal division by zero");
+    Decimal {
+        raw: (&a.raw * ONE + &b.raw / 2u32) / &b.raw,
+    }
+}
+
+binops!(Decimal; Add add add, Sub sub sub, Mul mul mul, Div div div);
+
+impl AddAssign<&Decimal> for Decimal {
+    fn add_assign(&mut self, rhs: &Decimal) {
+        self.raw += &rhs.raw;
+    }
+}
+
+impl AddAssign<Decimal> for Decimal {
+    fn add_assign(&mut self, rhs: Decimal) {
+        self.raw += rhs.raw;
+    }
+}
+
+impl SubAssign<&Decimal> for Decimal {
+    fn sub_assign(&mut self, rhs: &Decimal) {
+        *self = sub(self, rhs);
+    }
+}
+
+impl Sum for Decimal {
+    fn sum<I: Iterator<Item = Decimal>>(iter: I) -> Decimal {
+        iter.fold(Decimal::zero(), |sum, item| sum + item)
+    }
+}
+
+/// Plain decimal notation with no trailing zeros: `12`, `0.5`, `1.15`.
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let raw = format!("{:0>width$}", self.raw.to_string(), width = SCALE as usize + 1);
+        let (int, frac) = raw.split_at(raw.len() - SCALE as usize);
+        match frac.trim_end_matches('0') {
+            "" => f.write_str(int),
+            frac => write!(f, "{}.{}", int, frac),
+        }
+    }
+}
+
+impl fmt::Debug for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Parses plain decimal notation and `1.5e30` style exponents, as
+/// `BigFloat` writes them. Digits beyond `SCALE` round half up.
+impl FromStr for Decimal {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Decimal, String> {
+        let invalid = || format!("invalid decimal {:?}", s);
+        let (mantissa, exponent) = match s.split_once(['e', 'E']) {
+            Some((mantissa, exponent)) => (mantissa, exponent.parse::<i64>().map_err(|_| invalid())?),
+            None => (s, 0),
+        };
+        if exponent.abs() > MAX_EXPONENT {
+            return Err(format!("exponent of {:?} is out of range", s));
+        }
+        let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let all_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if int.is_empty() || !all_digits(int) || !all_digits(frac) {
+            return Err(invalid());
+        }
+        // Move the decimal point `exponent` places.
+        let digits = format!("{}{}", int, frac);
+        let point = int.len() as i64 + exponent;
+        let (int, frac) = if point <= 0 {
+            ("0".to_string(), format!("{}{}", "0".repeat(-point as usize), digits))
+        } else if point as usize >= digits.len() {
+            (format!("{:0<width$}", digits, width = point as usize), String::new())
+        } else {
+            let (int, frac) = digits.split_at(point as usize);
+            (int.to_string(), frac.to_string())
+        };
+        let scale = SCALE as usize;
+        let kept = &frac[..frac.len().min(scale)];
+        let raw: BigUint = format!("{}{:0<scale$}", int, kept)
+            .parse()
+            .map_err(|_| invalid())?;
+        let round_up = frac.as_bytes().get(scale).is_some_and(|&d| d >= b'5');
+        Ok(Decimal {
+            raw: if round_up { raw + 1u32 } else { raw },
+        })
+    }
+}
+
+impl Serialize for Decimal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_string().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Decimal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::number::d;
+
+    #[test]
+    fn parses_and_prints() {
+        let d = d::<Decimal>;
+        for s in ["0", "7", "0.5", "1.15", "123456789012345678901234567890.25"] {
+            assert_eq!(d(s).to_string(), s);
+        }
+        assert_eq!(d("1.50").to_string(), "1.5");
+        assert_eq!(d("007").to_string(), "7");
+        assert_eq!(d("0.0000000000000000005").to_string(), "0.000000000000000001");
+        assert_eq!(d("0.0000000000000000004").to_string(), "0");
+        assert_eq!(d("1.5e3").to_string(), "1500");
+        assert_eq!(d("2.5e-3").to_string(), "0.0025");
+        assert_eq!(d("1.23456789012346e29").to_string(), "123456789012346000000000000000");
+        assert!("1e9999999".parse::<Decimal>().is_err());
+        for bad in ["", "-1", "1.2.3", ".5", "1e", "e5", "abc"] {
+            assert!(bad.parse::<Decimal>().is_err(), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        let d = d::<Decimal>;
+        assert_eq!(d("1.5") + d("2.25"), d("3.75"));
+        assert_eq!(d("5") - d("0.5"), d("4.5"));
+        assert_eq!(d("1.15") * d("10"), d("11.5"));
+        assert_eq!(d("0.5") * d("0.5"), d("0.25"));
+        assert_eq!(d("1") / d("4"), d("0.25"));
+        assert_eq!(Decimal::from_ratio(115, 100), d("1.15"));
+        assert_eq!(Decimal::from_ratio(2, 3), d("0.666666666666666667"));
+        assert_eq!(d("0.5").checked_sub(&d("1")), None);
+        assert_eq!(d("7.9").floor(), d("7"));
+        assert_eq!(d("9").sqrt(), d("3"));
+        assert_eq!(d("2").sqrt(), d("1.414213562373095048"));
+        assert!((d("2e50").log10() - 50.30103).abs() < 1e-5);
+        assert_eq!([d("0.1"), d("0.2")].into_iter().sum::<Decimal>(), d("0.3"));
+        assert!(d("0.3") > d("0.25"));
+    }
+
+    #[test]
+    fn powers() {
+        let d = d::<Decimal>;
+        assert_eq!(d("2").pow(10), d("1024"));
+        assert_eq!(d("1.5").pow(2), d("2.25"));
+        assert_eq!(d("1.15").pow(0), d("1"));
+        assert_eq!(d("1.15").pow(3), d("1.520875"));
+        // Large powers stay exact in the whole part.
+        assert_eq!(d("10").pow(40).to_string(), format!("1{}", "0".repeat(40)));
+    }
+
+    #[test]
+    fn digits() {
+        let d = d::<Decimal>;
+        assert_eq!(d("12.5").digits(), ("125".to_string(), 1));
+        assert_eq!(d("1000").digits(), ("1".to_string(), 3));
+        assert_eq!(d("0.05").digits(), ("5".to_string(), -2));
+        assert_eq!(d("0").digits(), ("0".to_string(), 0));
+    }
+
+    #[test]
+    fn serializes_as_a_string() {
+        let d = d::<Decimal>;
+        assert_eq!(serde_json::to_string(&d("1.5")).unwrap(), r#""1.5""#);
+        // Saves from before fractions stored whole numbers the same way.
+        let whole = "123456789012345678901234567890";
+        let parsed: Decimal = serde_json::from_str(&format!("{:?}", whole)).unwrap();
+        assert_eq!(parsed, Decimal::from(whole.parse::<BigUint>().unwrap()));
+    }
+}
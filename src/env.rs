@@ -0,0 +1,96 @@
+//! The outside world the game depends on: a clock and somewhere to keep
+//! saves. The browser shell provides real implementations; the ones here let
+//! the simulation run natively.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+pub trait Clock {
+    /// Milliseconds since the Unix epoch.
+    fn now(&self) -> f64;
+
+    /// Milliseconds on a clock that never jumps, for measuring how long
+    /// something took. Only differences between readings mean anything.
+    fn monotonic(&self) -> f64 {
+        self.now()
+    }
+}
+
+/// Key-value storage for serialized saves.
+pub trait SaveStore {
+    fn get(&self, key: &str) -> Result<Option<String>, String>;
+    fn set(&self, key: &str, value: &str) -> Result<(), String>;
+}
+
+/// Everything the reducer needs besides the state itself.
+pub struct Env {
+    pub clock: Box<dyn Clock>,
+    pub store: Box<dyn SaveStore>,
+}
+
+impl Env {
+    /// `clock` with an empty [`MemoryStore`], for running the game natively
+    /// in tests and benchmarks.
+    pub fn in_memory(clock: impl Clock + 'static) -> Self {
+        Self {
+            clock: Box::new(clock),
+            store: Box::new(MemoryStore::default()),
+        }
+    }
+}
+
+impl<C: Clock> Clock for Rc<C> {
+    fn now(&self) -> f64 {
+        (**self).now()
+    }
+
+    fn monotonic(&self) -> f64 {
+        (**self).monotonic()
+    }
+}
+
+impl<S: SaveStore> SaveStore for Rc<S> {
+    fn get(&self, key: &str) -> Result<Option<String>, String> {
+        (**self).get(key)
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), String> {
+        (**self).set(key, value)
+    }
+}
+
+/// A clock that only moves when told to.
+#[derive(Default)]
+pub struct ManualClock(Cell<f64>);
+
+impl ManualClock {
+    pub fn new(now: f64) -> Self {
+        Self(Cell::new(now))
+    }
+
+    pub fn advance(&self, ms: f64) {
+        self.0.set(self.0.get() + ms);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> f64 {
+        self.0.get()
+    }
+}
+
+/// Saves kept in memory for the lifetime of the value.
+#[derive(Default)]
+pub struct MemoryStore(RefCell<HashMap<String, String>>);
+
+impl SaveStore for MemoryStore {
+    fn get(&self, key: &str) -> Result<Option<String>, String> {
+        Ok(self.0.borrow().get(key).cloned())
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), String> {
+        self.0.borrow_mut().insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+//! Why something the player asked for failed. The game's own reasons are
+//! shown in the player's language; messages from the browser or a JSON
+//! parser are passed on as they are.
+
+use std::fmt;
+
+use crate::locale::{Locale, Text};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    NotASaveCode,
+    SaveCodeCorrupted,
+    SaveCodeTruncated,
+    SaveCodeChecksum,
+    /// The code decoded, but not to a save this game can load.
+    SaveCodeInvalid(String),
+    BackupNotFound,
+    NothingSaved,
+    /// A sync saved first, but the save never made it to storage.
+    NotSaved,
+    SyncTokenRejected,
+    SyncStatus(u16),
+    SyncInvalidSave(String),
+    /// A message from the browser or a parser.
+    Other(String),
+}
+
+impl Error {
+    pub fn message(&self, locale: Locale) -> String {
+        let text = |text| locale.text(text).to_string();
+        match self {
+            Error::NotASaveCode => text(Text::ErrorNotASaveCode),
+            Error::SaveCodeCorrupted => text(Text::ErrorSaveCodeCorrupted),
+            Error::SaveCodeTruncated => text(Text::ErrorSaveCodeTruncated),
+            Error::SaveCodeChecksum => text(Text::ErrorSaveCodeChecksum),
+            Error::SaveCodeInvalid(e) => locale.fill(Text::ErrorSaveCodeInvalid, &[e]),
+            Error::BackupNotFound => text(Text::ErrorBackupNotFound),
+            Error::NothingSaved => text(Text::ErrorNothingSaved),
+            Error::NotSaved => text(Text::ErrorNotSaved),
+            Error::SyncTokenRejected => text(Text::ErrorSyncToken),
+            Error::SyncStatus(status) => locale.fill(Text::ErrorSyncStatus, &[&status.to_string()]),
+            Error::SyncInvalidSave(e) => locale.fill(Text::ErrorSyncInvalidSave, &[e]),
+            Error::Other(e) => e.clone(),
+        }
+    }
+}
+
+/// The English message, for logs.
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message(Locale::En))
+    }
+}
+
+impl From<String> for Error {
+    fn from(e: String) -> Self {
+        Error::Other(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_are_translated_around_what_they_pass_on() {
+        assert_eq!(Error::SyncStatus(500).message(Locale::En), "Sync server answered 500");
+        assert_eq!(Error::BackupNotFound.message(Locale::De), "Sicherung nicht gefunden");
+        assert_eq!(
+            Error::SaveCodeInvalid("missing counter".to_string()).message(Locale::Fr),
+            "Le code de sauvegarde n'est pas valide : missing counter"
+        );
+        assert_eq!(Error::Other("QuotaExceededError".to_string()).message(Locale::Es), "QuotaExceededError");
+    }
+}
@@ -0,0 +1,56 @@
+use crate::locale::{Locale, Text};
+
+/// A length of time in its two largest units, as `2h 5m`.
+pub fn format_duration(seconds: f64, locale: Locale) -> String {
+    let seconds = seconds as u64;
+    if seconds < 60 {
+        locale.fill(Text::Seconds, &[&seconds.to_string()])
+    } else if seconds < 3600 {
+        locale.fill(Text::MinutesSeconds, &[&(seconds / 60).to_string(), &(seconds % 60).to_string()])
+    } else {
+        let hours = locale.number(&(seconds / 3600).to_string());
+        locale.fill(Text::HoursMinutes, &[&hours, &(seconds % 3600 / 60).to_string()])
+    }
+}
+
+/// How long before `now` a timestamp was, or "Never" without one.
+pub fn format_time_ago(timestamp: Option<f64>, now: f64, locale: Locale) -> String {
+    timestamp.map_or(locale.text(Text::Never).to_string(), |timestamp| {
+        let seconds_ago = (now - timestamp) / 1000.0;
+        if seconds_ago < 60.0 {
+            locale.text(Text::JustNow).to_string()
+        } else if seconds_ago < 3600.0 {
+            let minutes = format!("{:.0}", seconds_ago / 60.0);
+            locale.fill(Text::MinutesAgo, &[&locale.number(&minutes)])
+        } else {
+            let hours = format!("{:.1}", seconds_ago / 3600.0);
+            locale.fill(Text::HoursAgo, &[&locale.number(&hours)])
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations() {
+        assert_eq!(format_duration(42.9, Locale::En), "42s");
+        assert_eq!(format_duration(125.0, Locale::En), "2m 5s");
+        assert_eq!(format_duration(7_500.0, Locale::En), "2h 5m");
+        assert_eq!(format_duration(125.0, Locale::De), "2 Min. 5 s");
+        assert_eq!(format_duration(7_500.0, Locale::Fr), "2 h 5 min");
+    }
+
+    #[test]
+    fn time_ago() {
+        let now = 10_000_000.0;
+        assert_eq!(format_time_ago(None, now, Locale::En), "Never");
+        assert_eq!(format_time_ago(Some(now - 5_000.0), now, Locale::En), "Just now");
+        assert_eq!(format_time_ago(Some(now - 300_000.0), now, Locale::En), "5 minutes ago");
+        assert_eq!(format_time_ago(Some(now - 5_400_000.0), now, Locale::En), "1.5 hours ago");
+        assert_eq!(format_time_ago(None, now, Locale::De), "Nie");
+        assert_eq!(format_time_ago(Some(now - 5_400_000.0), now, Locale::De), "vor 1,5 Stunden");
+        assert_eq!(format_time_ago(Some(now - 300_000.0), now, Locale::Fr), "il y a 5 minutes");
+    }
+}
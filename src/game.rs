@@ -0,0 +1,1274 @@
+use std::rc::Rc;
+
+use num_traits::Zero;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::achievements;
+use crate::backups;
+use crate::autosave::{SaveStatus, DEFAULT_AUTOSAVE_SECONDS};
+use crate::click::{click_upgrade_cost, ClickLimiter, ClickStats, BASE_CLICK_VALUE};
+use crate::cost::{production_upgrade_cost, CostCurve};
+use crate::number::Number;
+use crate::env::Env;
+use crate::error::Error;
+use crate::format::{format_duration, format_time_ago};
+use crate::generators::{starting_generators, GENERATORS};
+use crate::locale::{Locale, Text};
+use crate::notation::Notation;
+use crate::save::{self, CURRENT_SAVE_VERSION};
+use crate::save_code;
+use crate::slots;
+use crate::stats::Stats;
+use crate::undo::{Undo, UndoAction, UNDO_WINDOW_MS};
+use crate::upgrades::{self, Effects};
+
+/// Lifetime earnings needed for the first prestige point; more points need
+/// quadratically more (`points = sqrt(lifetime_earned / PRESTIGE_DIVISOR)`).
+const PRESTIGE_DIVISOR: u32 = 1_000_000;
+
+/// Production bonus per prestige point, in percent.
+const PRESTIGE_BONUS_PERCENT: u32 = 10;
+
+/// Limits on what a player earns while the game is closed.
+pub struct OfflineConfig {
+    /// Time away beyond this earns nothing extra.
+    pub max_seconds: u64,
+    /// Share of normal production earned while away.
+    pub efficiency_percent: u32,
+}
+
+pub const OFFLINE: OfflineConfig = OfflineConfig {
+    max_seconds: 12 * 60 * 60,
+    efficiency_percent: 50,
+};
+
+/// What offline progress was credited on load or after time away, for the
+/// welcome-back dialog.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OfflineReport {
+    pub away_seconds: f64,
+    pub credited_seconds: f64,
+    pub earned: Number,
+}
+
+impl OfflineReport {
+    pub fn summary(&self, notation: Notation, locale: Locale) -> String {
+        let mut summary = locale.fill(
+            Text::OfflineSummary,
+            &[
+                &format_duration(self.away_seconds, locale),
+                &notation.format(&self.earned, locale),
+            ],
+        );
+        if self.credited_seconds < self.away_seconds {
+            summary.push_str(&locale.fill(
+                Text::OfflineCapped,
+                &[&format_duration(self.credited_seconds, locale)],
+            ));
+        }
+        summary
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct State {
+    pub save_version: u32,
+    pub counter: Number,
+    pub generators: Vec<u32>,
+    pub upgrade_level: u32,
+    /// Everything ever produced, across prestiges.
+    pub lifetime_earned: Number,
+    /// Always a whole number.
+    pub prestige_points: Number,
+    /// Ids of unlocked achievements, in unlock order.
+    pub achievements: Vec<String>,
+    /// How the player wants big numbers written.
+    pub notation: Notation,
+    /// Seconds between autosaves.
+    pub autosave_seconds: u32,
+    /// Click-power upgrades bought; each doubles the click value.
+    pub click_level: u32,
+    pub click_stats: ClickStats,
+    /// Ids of purchased upgrade tree nodes, in purchase order.
+    pub upgrades: Vec<String>,
+    pub stats: Stats,
+    pub last_save: f64,
+    pub last_saved_at: Option<f64>,
+    /// Why the last import was rejected, shown until the next import attempt.
+    #[serde(skip)]
+    pub import_error: Option<Error>,
+    /// Why the last save or load failed, shown until one succeeds.
+    #[serde(skip)]
+    pub storage_error: Option<Error>,
+    /// Why the last save couldn't update the slot index or backups. The
+    /// save itself went through.
+    #[serde(skip)]
+    pub backup_error: Option<String>,
+    #[serde(skip)]
+    pub offline_report: Option<OfflineReport>,
+    /// Achievements unlocked since the player last saw a toast for them.
+    #[serde(skip)]
+    pub achievement_toasts: Vec<String>,
+    /// Save slot this game is played in.
+    #[serde(skip)]
+    pub slot: usize,
+    #[serde(skip)]
+    pub click_limiter: ClickLimiter,
+    /// Whether anything worth saving changed since the last save or load.
+    #[serde(skip)]
+    pub dirty: bool,
+    /// The game before the last reset, load, import, restore or sync, while it
+    /// can still be brought back.
+    #[serde(skip)]
+    pub undo: Option<Rc<Undo>>,
+}
+
+/// Something bought by the level, which can be bought in bulk.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Purchase {
+    Generator(usize),
+    ProductionUpgrade,
+    ClickUpgrade,
+}
+
+/// How many levels one press of a buy button buys.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuyAmount {
+    #[default]
+    One,
+    Ten,
+    Hundred,
+    /// As many as the counter covers.
+    Max,
+}
+
+pub const BUY_AMOUNTS: &[BuyAmount] = &[BuyAmount::One, BuyAmount::Ten, BuyAmount::Hundred, BuyAmount::Max];
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Msg {
+    /// Milliseconds of play since the previous tick.
+    Tick(u64),
+    /// Milliseconds the game wasn't running without being closed, as when
+    /// the machine was suspended. Credited under [`OFFLINE`] like a reload,
+    /// welcome-back report included.
+    TimeAway(u64),
+    UpgradeProduction,
+    BuyGenerator(usize),
+    Save,
+    /// A write the store had accepted failed afterwards, as IndexedDB writes
+    /// can. The game is unsaved again until the next save goes through.
+    WriteFailed(String),
+    Load,
+    Import(String),
+    DismissOfflineReport,
+    Prestige,
+    DismissAchievementToast(String),
+    SetNotation(Notation),
+    Reset,
+    /// A press of the click target.
+    Click,
+    UpgradeClick,
+    /// Buy the upgrade tree node with this id.
+    BuyUpgrade(String),
+    /// Buy several levels at once, all or nothing.
+    BuyMany(Purchase, BuyAmount),
+    /// Save if there are unsaved changes.
+    Autosave,
+    SetAutosaveInterval(u32),
+    /// Load the backup of this slot saved at this time.
+    RestoreBackup(f64),
+    /// Replace the game with a save from the sync server.
+    LoadSynced(String),
+    /// Take back the last reset, load, import, restore or sync.
+    Undo,
+    DismissUndo,
+}
+
+pub fn reducer(state: &State, msg: Msg, env: &Env) -> State {
+    // Saving, loading and closing dialogs don't change what would be saved.
+    let may_dirty = !matches!(
+        msg,
+        Msg::Save
+            | Msg::Autosave
+            | Msg::Load
+            | Msg::DismissOfflineReport
+            | Msg::DismissAchievementToast(_)
+            | Msg::DismissUndo
+    );
+    let now = env.clock.now();
+    let mut next = apply(state, msg, env);
+    achievements::unlock_new(&mut next);
+    if next.undo.as_ref().is_some_and(|undo| !undo.is_open(now)) {
+        next.undo = None;
+    }
+    if may_dirty && next != *state {
+        next.dirty = true;
+    }
+    next
+}
+
+fn apply(state: &State, msg: Msg, env: &Env) -> State {
+    match msg {
+        Msg::TimeAway(away_ms) => {
+            let mut next = state.clone();
+            next.credit_time_away(away_ms as f64, &OFFLINE);
+            next
+        }
+        Msg::Tick(elapsed_ms) => {
+            let production = state.production();
+            let earned = &production * Number::from_ratio(elapsed_ms, 1000);
+            let mut next = State {
+                counter: &state.counter + &earned,
+                lifetime_earned: &state.lifetime_earned + earned,
+                ..state.clone()
+            };
+            next.stats.online_ms += elapsed_ms;
+            next.stats.record_production(production);
+            next
+        }
+        Msg::UpgradeProduction => match state.upgrade_price() {
+            // Spend the price and double the production value.
+            Some(price) if state.counter >= price => {
+                let mut next = State {
+                    counter: &state.counter - &price,
+                    upgrade_level: state.upgrade_level + 1,
+                    ..state.clone()
+                };
+                next.stats.upgrades_purchased += 1;
+                next
+            }
+            // Unaffordable or sold out: nothing changes.
+            _ => state.clone(),
+        },
+        Msg::BuyGenerator(tier) => match state.generator_price(tier) {
+            Some(price) if state.counter >= price => {
+                let mut generators = state.generators.clone();
+                generators[tier] += 1;
+                State {
+                    counter: &state.counter - &price,
+                    generators,
+                    ..state.clone()
+                }
+            }
+            _ => state.clone(),
+        },
+        Msg::Save => state.save(env).unwrap_or_else(|e| State {
+            storage_error: Some(e.into()),
+            ..state.clone()
+        }),
+        Msg::WriteFailed(e) => State {
+            storage_error: Some(Error::Other(e)),
+            dirty: true,
+            ..state.clone()
+        },
+        Msg::Load => match State::load(env, state.slot) {
+            Ok(Some(mut loaded)) => {
+                loaded.stats.loads += 1;
+                loaded.undoable(state, UndoAction::Load, env.clock.now())
+            }
+            Ok(None) => state.clone(),
+            Err(e) => State {
+                storage_error: Some(e.into()),
+                ..state.clone()
+            },
+        },
+        Msg::Import(code) => match State::import(&code, env.clock.now()) {
+            Ok(imported) => State {
+                slot: state.slot,
+                ..imported
+            }
+            .undoable(state, UndoAction::Import, env.clock.now()),
+            Err(e) => State {
+                import_error: Some(e),
+                ..state.clone()
+            },
+        },
+        Msg::DismissOfflineReport => State {
+            offline_report: None,
+            ..state.clone()
+        },
+        Msg::Prestige => {
+            let pending = state.pending_prestige_points();
+            if pending.is_zero() {
+                return state.clone();
+            }
+            // Start the run over, keeping only lifetime progress.
+            State {
+                counter: Number::zero(),
+                generators: starting_generators(),
+                upgrade_level: 0,
+                upgrades: Vec::new(),
+                prestige_points: &state.prestige_points + pending,
+                ..state.clone()
+            }
+        }
+        Msg::DismissAchievementToast(id) => State {
+            achievement_toasts: state
+                .achievement_toasts
+                .iter()
+                .filter(|toast| **toast != id)
+                .cloned()
+                .collect(),
+            ..state.clone()
+        },
+        Msg::SetNotation(notation) => State {
+            notation,
+            ..state.clone()
+        },
+        Msg::Click => {
+            let mut next = state.clone();
+            if !next.click_limiter.allow(env.clock.monotonic()) {
+                next.click_stats.rejected += 1;
+                return next;
+            }
+            let value = state.click_value();
+            next.counter += &value;
+            next.lifetime_earned += &value;
+            next.click_stats.clicks += 1;
+            next.click_stats.earned += value;
+            next
+        }
+        Msg::UpgradeClick => match state.click_upgrade_price() {
+            Some(price) if state.counter >= price => {
+                let mut next = State {
+                    counter: &state.counter - &price,
+                    click_level: state.click_level + 1,
+                    ..state.clone()
+                };
+                next.stats.upgrades_purchased += 1;
+                next
+            }
+            _ => state.clone(),
+        },
+        Msg::BuyUpgrade(id) => match upgrades::find(&id) {
+            Some(node) if state.can_buy_upgrade(&id) => {
+                let mut upgrades = state.upgrades.clone();
+                upgrades.push(id);
+                let mut next = State {
+                    counter: &state.counter - node.price(),
+                    upgrades,
+                    ..state.clone()
+                };
+                next.stats.upgrades_purchased += 1;
+                next
+            }
+            _ => state.clone(),
+        },
+        Msg::BuyMany(purchase, amount) => match state.bulk_price(purchase, amount) {
+            Some((quantity, price)) if quantity > 0 && state.counter >= price => {
+                let mut next = State {
+                    counter: &state.counter - &price,
+                    ..state.clone()
+                };
+                match purchase {
+                    Purchase::Generator(tier) => next.generators[tier] += quantity,
+                    Purchase::ProductionUpgrade => next.upgrade_level += quantity,
+                    Purchase::ClickUpgrade => next.click_level += quantity,
+                }
+                if !matches!(purchase, Purchase::Generator(_)) {
+                    next.stats.upgrades_purchased += u64::from(quantity);
+                }
+                next
+            }
+            _ => state.clone(),
+        },
+        Msg::RestoreBackup(saved_at) => {
+            let restored = backups::find(env.store.as_ref(), state.slot, saved_at)
+                .map_err(Error::from)
+                .and_then(|backup| backup.ok_or(Error::BackupNotFound))
+                .and_then(|backup| State::load_json(&backup.save, env.clock.now(), state.slot).map_err(Error::from));
+            match restored {
+                Ok(mut restored) => {
+                    restored.stats.loads += 1;
+                    restored.undoable(state, UndoAction::RestoreBackup, env.clock.now())
+                }
+                Err(e) => State {
+                    storage_error: Some(e),
+                    ..state.clone()
+                },
+            }
+        }
+        Msg::Autosave if state.dirty => apply(state, Msg::Save, env),
+        Msg::Autosave => state.clone(),
+        Msg::SetAutosaveInterval(seconds) => State {
+            autosave_seconds: seconds.max(1),
+            ..state.clone()
+        },
+        // Settings outlive the progress being reset.
+        Msg::Reset => State {
+            slot: state.slot,
+            notation: state.notation,
+            autosave_seconds: state.autosave_seconds,
+            stats: Stats {
+                resets: state.stats.resets + 1,
+                ..state.stats.clone()
+            },
+            ..State::new(env.clock.now())
+        }
+        .undoable(state, UndoAction::Reset, env.clock.now()),
+        Msg::LoadSynced(json) => match State::load_json(&json, env.clock.now(), state.slot) {
+            Ok(mut synced) => {
+                synced.stats.loads += 1;
+                synced.undoable(state, UndoAction::Sync, env.clock.now())
+            }
+            Err(e) => State {
+                storage_error: Some(e.into()),
+                ..state.clone()
+            },
+        },
+        // Storage is left alone: the next save writes the old game back.
+        Msg::Undo => match &state.undo {
+            Some(undo) if undo.is_open(env.clock.now()) => undo.previous.clone(),
+            _ => state.clone(),
+        },
+        Msg::DismissUndo => State {
+            undo: None,
+            ..state.clone()
+        },
+    }
+}
+
+impl State {
+    /// Keep `previous` so the action that replaced it with `self` can be
+    /// undone until `now + UNDO_WINDOW_MS`.
+    fn undoable(self, previous: &State, action: UndoAction, now: f64) -> Self {
+        let undo = Undo {
+            action,
+            previous: State {
+                undo: None,
+                ..previous.clone()
+            },
+            expires_at: now + UNDO_WINDOW_MS,
+        };
+        State {
+            undo: Some(Rc::new(undo)),
+            ..self
+        }
+    }
+
+    pub fn new(now: f64) -> Self {
+        Self {
+            save_version: CURRENT_SAVE_VERSION,
+            counter: Number::zero(),
+            generators: starting_generators(),
+            upgrade_level: 0,
+            lifetime_earned: Number::zero(),
+            prestige_points: Number::zero(),
+            achievements: Vec::new(),
+            notation: Notation::default(),
+            autosave_seconds: DEFAULT_AUTOSAVE_SECONDS,
+            click_level: 0,
+            click_stats: ClickStats::default(),
+            upgrades: Vec::new(),
+            stats: Stats::new(now),
+            last_save: now,
+            last_saved_at: None,
+            import_error: None,
+            storage_error: None,
+            backup_error: None,
+            offline_report: None,
+            achievement_toasts: Vec::new(),
+            slot: 0,
+            click_limiter: ClickLimiter::default(),
+            dirty: false,
+            undo: None,
+        }
+    }
+
+    /// Counter gained per second: every generator's output scaled by its
+    /// tree upgrades, then by global tree upgrades, doubled once per
+    /// production upgrade and boosted by prestige points and achievements.
+    pub fn production(&self) -> Number {
+        let effects = self.upgrade_effects();
+        let base: Number = GENERATORS
+            .iter()
+            .zip(&self.generators)
+            .zip(&effects.tiers)
+            .map(|((generator, &owned), tier)| {
+                Number::from(generator.base_output) * Number::from(owned) * tier
+            })
+            .sum();
+        let hundred = Number::from(100u32);
+        let achievement_percent =
+            Number::from(achievements::bonus_percent(&self.achievements) + 100);
+        base * effects.global
+            * Number::from(2u32).pow(self.upgrade_level)
+            * self.prestige_multiplier_percent()
+            / &hundred
+            * achievement_percent
+            / hundred
+    }
+
+    /// What current production earns over `elapsed_ms` of play.
+    pub fn earned_in(&self, elapsed_ms: u64) -> Number {
+        self.production() * Number::from_ratio(elapsed_ms, 1000)
+    }
+
+    pub fn has_achievement(&self, id: &str) -> bool {
+        self.achievements.iter().any(|unlocked| unlocked == id)
+    }
+
+    pub fn prestige_multiplier_percent(&self) -> Number {
+        &self.prestige_points * Number::from(PRESTIGE_BONUS_PERCENT) + Number::from(100u32)
+    }
+
+    /// Points a prestige would award now: the total earned by lifetime
+    /// earnings minus those already claimed.
+    pub fn pending_prestige_points(&self) -> Number {
+        let total = (&self.lifetime_earned / Number::from(PRESTIGE_DIVISOR)).sqrt().floor();
+        total
+            .checked_sub(&self.prestige_points)
+            .unwrap_or_default()
+    }
+
+    pub fn generator_price(&self, tier: usize) -> Option<Number> {
+        let owned = *self.generators.get(tier)?;
+        GENERATORS[tier].cost_curve().price(owned)
+    }
+
+    pub fn can_afford_generator(&self, tier: usize) -> bool {
+        self.generator_price(tier)
+            .is_some_and(|price| self.counter >= price)
+    }
+
+    pub fn upgrade_price(&self) -> Option<Number> {
+        production_upgrade_cost().price(self.upgrade_level)
+    }
+
+    pub fn can_afford_upgrade(&self) -> bool {
+        self.upgrade_price()
+            .is_some_and(|price| self.counter >= price)
+    }
+
+    /// Counter earned by one click.
+    pub fn click_value(&self) -> Number {
+        Number::from(BASE_CLICK_VALUE)
+            * Number::from(2u32).pow(self.click_level)
+            * self.upgrade_effects().click
+    }
+
+    pub fn upgrade_effects(&self) -> Effects {
+        upgrades::effects(&self.upgrades)
+    }
+
+    pub fn has_upgrade(&self, id: &str) -> bool {
+        self.upgrades.iter().any(|purchased| purchased == id)
+    }
+
+    /// Whether every prerequisite of node `id` has been bought.
+    pub fn upgrade_unlocked(&self, id: &str) -> bool {
+        upgrades::find(id)
+            .is_some_and(|node| node.requires.iter().all(|required| self.has_upgrade(required)))
+    }
+
+    pub fn can_buy_upgrade(&self, id: &str) -> bool {
+        !self.has_upgrade(id)
+            && self.upgrade_unlocked(id)
+            && upgrades::find(id).is_some_and(|node| self.counter >= node.price())
+    }
+
+    pub fn click_upgrade_price(&self) -> Option<Number> {
+        click_upgrade_cost().price(self.click_level)
+    }
+
+    pub fn can_afford_click_upgrade(&self) -> bool {
+        self.click_upgrade_price()
+            .is_some_and(|price| self.counter >= price)
+    }
+
+    fn purchase_curve(&self, purchase: Purchase) -> Option<(CostCurve, u32)> {
+        Some(match purchase {
+            Purchase::Generator(tier) => {
+                (GENERATORS.get(tier)?.cost_curve(), *self.generators.get(tier)?)
+            }
+            Purchase::ProductionUpgrade => (production_upgrade_cost(), self.upgrade_level),
+            Purchase::ClickUpgrade => (click_upgrade_cost(), self.click_level),
+        })
+    }
+
+    /// How many levels `amount` buys and what they cost together. `Max`
+    /// with nothing affordable quotes a single level.
+    pub fn bulk_price(&self, purchase: Purchase, amount: BuyAmount) -> Option<(u32, Number)> {
+        let (curve, level) = self.purchase_curve(purchase)?;
+        let quantity = match amount {
+            BuyAmount::One => 1,
+            BuyAmount::Ten => 10,
+            BuyAmount::Hundred => 100,
+            BuyAmount::Max => match curve.max_affordable(level, &self.counter) {
+                0 => return curve.price(level).map(|price| (0, price)),
+                quantity => quantity,
+            },
+        };
+        Some((quantity, curve.total_price(level, quantity)?))
+    }
+
+    pub fn can_afford_bulk(&self, purchase: Purchase, amount: BuyAmount) -> bool {
+        self.bulk_price(purchase, amount)
+            .is_some_and(|(quantity, price)| quantity > 0 && self.counter >= price)
+    }
+
+    /// The state as it should be persisted at `now`.
+    fn snapshot(&self, now: f64) -> State {
+        let mut state = self.clone();
+        // Offline progress on the next load is counted from this moment.
+        state.last_save = now;
+        state.last_saved_at = Some(now);
+        state
+    }
+
+    /// Persist the game to its slot, returning the state as saved. Only the
+    /// slot write can fail the save; the slot index and backups are
+    /// bookkeeping, and a failure there ends up in `backup_error`.
+    pub fn save(&self, env: &Env) -> Result<State, String> {
+        let mut saved = self.snapshot(env.clock.now());
+        saved.storage_error = None;
+        saved.dirty = false;
+        saved.stats.saves += 1;
+        let json = serde_json::to_string(&saved).map_err(|e| e.to_string())?;
+        env.store.set(&slots::slot_key(self.slot), &json)?;
+        saved.backup_error = slots::record_save(env.store.as_ref(), &saved)
+            .and_then(|()| backups::record(env.store.as_ref(), &saved, &json))
+            .err();
+        Ok(saved)
+    }
+
+    /// Read the game stored in `slot`, if there is one.
+    pub fn load(env: &Env, slot: usize) -> Result<Option<Self>, String> {
+        let Some(json) = env.store.get(&slots::slot_key(slot))? else {
+            return Ok(None);
+        };
+        State::load_json(&json, env.clock.now(), slot).map(Some)
+    }
+
+    /// Turn a save as `save` wrote it into the game running in `slot`.
+    pub fn load_json(json: &str, now: f64, slot: usize) -> Result<Self, String> {
+        let save = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let state = State::restore(save, now)?;
+        Ok(State { slot, ..state })
+    }
+
+    pub fn export(&self, now: f64) -> String {
+        let json = serde_json::to_string(&self.snapshot(now)).expect("State always serializes");
+        save_code::encode(&json)
+    }
+
+    pub fn import(code: &str, now: f64) -> Result<Self, Error> {
+        let json = save_code::decode(code)?;
+        let save = serde_json::from_str(&json).map_err(|_| Error::SaveCodeCorrupted)?;
+        State::restore(save, now).map_err(Error::SaveCodeInvalid)
+    }
+
+    /// Turn a stored save into the running game, crediting offline progress.
+    fn restore(save: Value, now: f64) -> Result<Self, String> {
+        let mut state = State::from_save(save)?;
+        state.stats.session_started_at = now;
+        state.apply_offline_progress(now, &OFFLINE);
+        achievements::unlock_new(&mut state);
+        Ok(state)
+    }
+
+    /// Credit production for the time since `last_save`, capped and scaled by
+    /// `config`, and leave a report for the welcome-back dialog.
+    pub fn apply_offline_progress(&mut self, now: f64, config: &OfflineConfig) {
+        let away_ms = (now - self.last_save).max(0.0);
+        self.last_save = now;
+        self.credit_time_away(away_ms, config);
+    }
+
+    /// Credit production for `away_ms` of time away, capped and scaled by
+    /// `config`, and leave a report for the welcome-back dialog unless it
+    /// came to nothing.
+    fn credit_time_away(&mut self, away_ms: f64, config: &OfflineConfig) {
+        let credited_ms = away_ms.min(config.max_seconds as f64 * 1000.0) as u64;
+        self.stats.offline_ms += away_ms as u64;
+        let efficiency_percent =
+            (config.efficiency_percent + self.upgrade_effects().offline_efficiency_percent).min(100);
+        let earned = self.production()
+            * Number::from_ratio(credited_ms, 1000)
+            * Number::from_ratio(efficiency_percent.into(), 100);
+        if earned.is_zero() {
+            return;
+        }
+        self.counter += &earned;
+        self.lifetime_earned += &earned;
+        self.offline_report = Some(OfflineReport {
+            away_seconds: away_ms / 1000.0,
+            credited_seconds: credited_ms as f64 / 1000.0,
+            earned,
+        });
+    }
+
+    /// Bring a stored save of any version up to date and deserialize it.
+    pub fn from_save(save: Value) -> Result<Self, String> {
+        let mut state: State =
+            serde_json::from_value(save::migrate(save)?).map_err(|e| e.to_string())?;
+        state.generators.resize(GENERATORS.len(), 0);
+        Ok(state)
+    }
+
+    pub fn save_status(&self) -> SaveStatus {
+        match &self.storage_error {
+            Some(e) => SaveStatus::Error(e.clone()),
+            None if self.dirty => SaveStatus::Pending,
+            None => SaveStatus::Saved,
+        }
+    }
+
+    pub fn format_last_saved(&self, now: f64, locale: Locale) -> String {
+        format_time_ago(self.last_saved_at, now, locale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::number::d;
+    use crate::click::MAX_CLICKS_PER_SECOND;
+    use crate::env::{Clock, ManualClock, MemoryStore, SaveStore};
+
+    fn state_with(counter: u32) -> State {
+        State {
+            counter: Number::from(counter),
+            lifetime_earned: Number::from(counter),
+            ..State::new(0.0)
+        }
+    }
+
+    fn apply(state: &State, msg: Msg) -> State {
+        reducer(state, msg, &Env::in_memory(ManualClock::default()))
+    }
+
+    #[test]
+    fn upgrade_spends_counter() {
+        let state = apply(&state_with(30), Msg::UpgradeProduction);
+        assert_eq!(state.counter, d("20"));
+        assert_eq!(state.production(), d("2"));
+        assert_eq!(state.upgrade_level, 1);
+        assert_eq!(state.upgrade_price(), Some(d("25")));
+    }
+
+    #[test]
+    fn unaffordable_upgrade_is_rejected() {
+        let before = state_with(9);
+        assert!(!before.can_afford_upgrade());
+        assert_eq!(apply(&before, Msg::UpgradeProduction), before);
+    }
+
+    #[test]
+    fn generators_add_to_production() {
+        let state = apply(&state_with(100), Msg::BuyGenerator(1));
+        assert_eq!(state.counter, Number::zero());
+        assert_eq!(state.generators[1], 1);
+        assert_eq!(state.production(), d("9"));
+        assert_eq!(state.generator_price(1), Some(d("115")));
+        assert_eq!(apply(&state, Msg::BuyGenerator(1)), state);
+    }
+
+    #[test]
+    fn v1_save_loads_with_same_production() {
+        let save = serde_json::from_str(include_str!("../fixtures/saves/v1_late_game.json")).unwrap();
+        let state = State::from_save(save).unwrap();
+        assert_eq!(state.generators, starting_generators());
+        assert_eq!(state.production(), d("1024"));
+
+        let round_trip = State::from_save(serde_json::to_value(&state).unwrap()).unwrap();
+        assert_eq!(round_trip, state);
+    }
+
+    #[test]
+    fn import_rejects_bad_codes_visibly() {
+        let state = apply(&state_with(5), Msg::Import("IG1.garbage".to_string()));
+        assert_eq!(state.import_error, Some(Error::SaveCodeCorrupted));
+        assert_eq!(state.counter, d("5"));
+
+        let not_a_save = save_code::encode(r#"{"counter":"1"}"#);
+        let state = apply(&state, Msg::Import(not_a_save));
+        assert!(matches!(state.import_error, Some(Error::SaveCodeInvalid(_))));
+    }
+
+    #[test]
+    fn export_then_import_restores_progress() {
+        let mut state = state_with(1234);
+        state.generators[2] = 4;
+        let imported = State::import(&state.export(1000.0), 1000.0).unwrap();
+        assert_eq!(imported.counter, state.counter);
+        assert_eq!(imported.generators, state.generators);
+    }
+
+    #[test]
+    fn offline_progress_is_capped_and_scaled() {
+        let config = OfflineConfig { max_seconds: 3600, efficiency_percent: 50 };
+
+        let mut state = state_with(0);
+        state.apply_offline_progress(90_500.0, &config);
+        assert_eq!(state.counter, d("45.25"));
+        assert_eq!(state.last_save, 90_500.0);
+        assert_eq!(state.offline_report.as_ref().unwrap().credited_seconds, 90.5);
+
+        let mut state = state_with(0);
+        state.apply_offline_progress(10.0 * 3_600_000.0, &config);
+        assert_eq!(state.counter, d("1800"));
+        let report = state.offline_report.unwrap();
+        assert_eq!(report.away_seconds, 36_000.0);
+        assert_eq!(report.credited_seconds, 3600.0);
+    }
+
+    #[test]
+    fn no_offline_progress_without_time_away() {
+        let config = OfflineConfig { max_seconds: 3600, efficiency_percent: 50 };
+        let mut state = state_with(0);
+        state.last_save = 5000.0;
+        state.apply_offline_progress(4000.0, &config);
+        assert_eq!(state.counter, Number::zero());
+        assert_eq!(state.offline_report, None);
+    }
+
+    #[test]
+    fn save_and_load_go_through_the_store() {
+        let clock = Rc::new(ManualClock::new(0.0));
+        let env = Env::in_memory(clock.clone());
+        assert_eq!(State::load(&env, 0), Ok(None));
+
+        let state = reducer(&state_with(500), Msg::Save, &env);
+        assert_eq!(state.last_saved_at, Some(0.0));
+
+        clock.advance(10_000.0);
+        let loaded = reducer(&State::new(clock.now()), Msg::Load, &env);
+        assert_eq!(loaded.counter, d("505"));
+        assert_eq!(loaded.offline_report.unwrap().credited_seconds, 10.0);
+    }
+
+    struct FullStore;
+
+    impl SaveStore for FullStore {
+        fn get(&self, _key: &str) -> Result<Option<String>, String> {
+            Ok(Some("{ not json".to_string()))
+        }
+
+        fn set(&self, _key: &str, _value: &str) -> Result<(), String> {
+            Err("QuotaExceededError".to_string())
+        }
+    }
+
+    #[test]
+    fn storage_failures_are_reported() {
+        let env = Env {
+            clock: Box::new(ManualClock::new(0.0)),
+            store: Box::new(FullStore),
+        };
+        let state = reducer(&state_with(5), Msg::Save, &env);
+        assert_eq!(state.storage_error, Some(Error::Other("QuotaExceededError".to_string())));
+        assert_eq!(state.last_saved_at, None);
+
+        let state = reducer(&state, Msg::Load, &env);
+        assert!(state.storage_error.is_some());
+        assert_eq!(state.counter, d("5"));
+    }
+
+    /// Has room for saves but not for backups.
+    #[derive(Default)]
+    struct NoRoomForBackups(MemoryStore);
+
+    impl SaveStore for NoRoomForBackups {
+        fn get(&self, key: &str) -> Result<Option<String>, String> {
+            self.0.get(key)
+        }
+
+        fn set(&self, key: &str, value: &str) -> Result<(), String> {
+            if key.starts_with("idle_game_backups") {
+                return Err("QuotaExceededError".to_string());
+            }
+            self.0.set(key, value)
+        }
+    }
+
+    #[test]
+    fn failed_backups_do_not_fail_the_save() {
+        let env = Env {
+            clock: Box::new(ManualClock::new(0.0)),
+            store: Box::new(NoRoomForBackups::default()),
+        };
+        let state = reducer(&state_with(5), Msg::Tick(1000), &env);
+        let saved = reducer(&state, Msg::Save, &env);
+        assert_eq!(saved.storage_error, None);
+        assert_eq!(saved.backup_error.as_deref(), Some("QuotaExceededError"));
+        assert_eq!(saved.save_status(), SaveStatus::Saved);
+        assert_eq!(State::load(&env, 0).unwrap().unwrap().counter, saved.counter);
+    }
+
+    #[test]
+    fn prestige_resets_run_and_awards_points() {
+        let mut state = state_with(50);
+        state.generators[1] = 3;
+        state.upgrade_level = 2;
+        state.lifetime_earned = d("9500000");
+        assert_eq!(state.pending_prestige_points(), d("3"));
+
+        let state = apply(&state, Msg::Prestige);
+        assert_eq!(state.counter, Number::zero());
+        assert_eq!(state.generators, starting_generators());
+        assert_eq!(state.upgrade_level, 0);
+        assert_eq!(state.prestige_points, d("3"));
+        assert_eq!(state.lifetime_earned, d("9500000"));
+        assert!(state.pending_prestige_points().is_zero());
+        // One cursor at +30%, and +5% from the first-prestige achievement.
+        assert!(state.has_achievement("prestige_1"));
+        assert_eq!(state.production(), d("1.365"));
+        let mut boosted = state.clone();
+        boosted.generators[0] = 10;
+        assert_eq!(boosted.production(), d("13.65"));
+
+        assert_eq!(apply(&state, Msg::Prestige), state);
+    }
+
+    #[test]
+    fn achievements_unlock_after_transitions() {
+        let state = apply(&state_with(999), Msg::Tick(1000));
+        assert!(state.has_achievement("counter_1k"));
+        assert_eq!(state.achievement_toasts, vec!["counter_1k"]);
+
+        let state = apply(&state, Msg::DismissAchievementToast("counter_1k".to_string()));
+        assert!(state.achievement_toasts.is_empty());
+        assert!(state.has_achievement("counter_1k"));
+    }
+
+    #[test]
+    fn achievement_bonuses_boost_production() {
+        let mut state = state_with(0);
+        state.generators[0] = 100;
+        state.achievements = vec!["counter_1m".to_string(), "prestige_1".to_string()];
+        assert_eq!(state.production(), d("107"));
+    }
+
+    #[test]
+    fn prices_and_production_keep_fractions() {
+        let mut state = state_with(0);
+        state.generators[0] = 3;
+        assert_eq!(state.generator_price(0), Some(d("15.20875")));
+        state.counter = d("15.20875");
+        let state = apply(&state, Msg::BuyGenerator(0));
+        assert!(state.counter.is_zero());
+        assert_eq!(state.generator_price(0), Some(d("17.4900625")));
+
+        let mut state = state_with(0);
+        state.prestige_points = d("1");
+        assert_eq!(state.production(), d("1.1"));
+        assert_eq!(apply(&state, Msg::Tick(1000)).counter, d("1.1"));
+    }
+
+    #[test]
+    fn ticks_credit_the_time_that_passed() {
+        let mut state = state_with(0);
+        state.generators[0] = 10;
+        // Irregular gaps, as from a throttled background tab, that add up
+        // to a minute.
+        for elapsed_ms in [1000, 16, 984, 250, 750, 57_000] {
+            state = apply(&state, Msg::Tick(elapsed_ms));
+        }
+        assert_eq!(state.counter, d("600"));
+        assert_eq!(apply(&state, Msg::Tick(0)), state);
+        assert_eq!(apply(&state_with(0), Msg::Tick(1)).counter, d("0.001"));
+    }
+
+    #[test]
+    fn a_night_away_unlocks_its_achievement_without_a_reload() {
+        let env = Env::in_memory(ManualClock::new(0.0));
+        let state = reducer(&state_with(0), Msg::TimeAway(7 * 3_600_000), &env);
+        assert!(!state.has_achievement("offline_8h"));
+        let state = reducer(&state_with(0), Msg::TimeAway(8 * 3_600_000), &env);
+        assert!(state.has_achievement("offline_8h"));
+    }
+
+    #[test]
+    fn time_away_is_capped_and_scaled_like_a_reload() {
+        let mut state = state_with(0);
+        state.generators[0] = 10;
+        // A day suspended: capped at twelve hours, at half production.
+        let away = apply(&state, Msg::TimeAway(24 * 3_600_000));
+        assert_eq!(away.counter, d("216000"));
+        assert_eq!(away.stats.offline_ms, 24 * 3_600_000);
+        assert_eq!(away.stats.online_ms, 0);
+        assert_eq!(away.offline_report.unwrap().credited_seconds, 43_200.0);
+        // A long tick, as from a hidden tab, is still played time.
+        assert_eq!(apply(&state, Msg::Tick(24 * 3_600_000)).counter, d("864000"));
+    }
+
+    #[test]
+    fn clicks_earn_the_click_value() {
+        let state = apply(&state_with(0), Msg::Click);
+        assert_eq!(state.counter, d("1"));
+        assert_eq!(state.lifetime_earned, d("1"));
+        assert_eq!(state.click_stats.clicks, 1);
+        assert_eq!(state.click_stats.earned, d("1"));
+    }
+
+    #[test]
+    fn click_upgrades_double_the_click_value() {
+        let state = apply(&state_with(30), Msg::UpgradeClick);
+        assert_eq!(state.counter, d("5"));
+        assert_eq!(state.click_level, 1);
+        assert_eq!(state.click_value(), d("2"));
+        assert_eq!(state.click_upgrade_price(), Some(d("100")));
+        assert_eq!(apply(&state, Msg::UpgradeClick), state);
+    }
+
+    #[test]
+    fn clicks_past_the_rate_cap_are_ignored() {
+        let clock = Rc::new(ManualClock::new(0.0));
+        let env = Env::in_memory(clock.clone());
+        let mut state = state_with(0);
+        for _ in 0..MAX_CLICKS_PER_SECOND + 5 {
+            state = reducer(&state, Msg::Click, &env);
+        }
+        assert_eq!(state.counter, Number::from(MAX_CLICKS_PER_SECOND as u64));
+        assert_eq!(state.click_stats.rejected, 5);
+
+        clock.advance(1000.0);
+        state = reducer(&state, Msg::Click, &env);
+        assert_eq!(state.click_stats.clicks, MAX_CLICKS_PER_SECOND as u64 + 1);
+    }
+
+    fn with_upgrades(mut state: State, ids: &[&str]) -> State {
+        state.upgrades = ids.iter().map(|id| id.to_string()).collect();
+        state
+    }
+
+    #[test]
+    fn upgrade_nodes_need_their_prerequisites() {
+        let state = state_with(2_000);
+        assert!(!state.can_buy_upgrade("fertile_fields"));
+        let rejected = apply(&state, Msg::BuyUpgrade("fertile_fields".to_string()));
+        assert_eq!((rejected.counter, rejected.upgrades), (state.counter.clone(), vec![]));
+
+        let state = apply(&state, Msg::BuyUpgrade("sharper_cursors".to_string()));
+        assert_eq!(state.counter, d("1900"));
+        let state = apply(&state, Msg::BuyUpgrade("fertile_fields".to_string()));
+        assert_eq!(state.counter, d("900"));
+        assert_eq!(state.upgrades, vec!["sharper_cursors", "fertile_fields"]);
+
+        // Nodes are bought once, and unknown ids are ignored.
+        let again = apply(&state_with(2_000), Msg::BuyUpgrade("sharper_cursors".to_string()));
+        assert_eq!(apply(&again, Msg::BuyUpgrade("sharper_cursors".to_string())), again);
+        assert_eq!(apply(&state, Msg::BuyUpgrade("nope".to_string())), state);
+    }
+
+    #[test]
+    fn upgrade_effects_compose_into_production() {
+        let mut state = state_with(0);
+        state.generators = vec![2, 1, 0, 0, 0];
+        assert_eq!(state.production(), d("10"));
+        // Cursors x2: 4 + 8.
+        let state = with_upgrades(state, &["sharper_cursors"]);
+        assert_eq!(state.production(), d("12"));
+        // Then everything x1.5, and the doubling upgrade on top.
+        let state = State {
+            upgrade_level: 1,
+            ..with_upgrades(state, &["sharper_cursors", "synergy"])
+        };
+        assert_eq!(state.production(), d("36"));
+    }
+
+    #[test]
+    fn upgrades_boost_clicks_and_offline_progress() {
+        let state = with_upgrades(state_with(0), &["strong_fingers"]);
+        assert_eq!(apply(&state, Msg::Click).counter, d("3"));
+
+        let mut state = with_upgrades(state_with(0), &["night_shift"]);
+        state.apply_offline_progress(10_000.0, &OFFLINE);
+        assert_eq!(state.counter, d("6.5"));
+    }
+
+    #[test]
+    fn prestige_clears_the_upgrade_tree() {
+        let state = with_upgrades(state_with(1_000_000), &["sharper_cursors"]);
+        assert!(apply(&state, Msg::Prestige).upgrades.is_empty());
+    }
+
+    #[test]
+    fn bulk_buys_are_all_or_nothing() {
+        // Ten cursors from one owned: 11.5 * (1.15^10 - 1) / 0.15.
+        let state = state_with(234);
+        let buy_ten = Msg::BuyMany(Purchase::Generator(0), BuyAmount::Ten);
+        let (quantity, price) = state.bulk_price(Purchase::Generator(0), BuyAmount::Ten).unwrap();
+        assert_eq!(quantity, 10);
+        assert!(price > d("233.49") && price < d("233.5"));
+        let bought = apply(&state, buy_ten.clone());
+        assert_eq!(bought.generators[0], 11);
+        assert_eq!(bought.counter, &state.counter - &price);
+
+        let short = state_with(233);
+        assert_eq!(apply(&short, buy_ten).generators[0], 1);
+    }
+
+    #[test]
+    fn max_buys_as_many_as_the_counter_covers() {
+        // Production upgrades cost 10, 25, 62.5, ...
+        let state = apply(&state_with(100), Msg::BuyMany(Purchase::ProductionUpgrade, BuyAmount::Max));
+        assert_eq!(state.upgrade_level, 3);
+        assert_eq!(state.counter, d("2.5"));
+
+        // Nothing affordable quotes the next level and buys nothing.
+        assert_eq!(
+            state.bulk_price(Purchase::ProductionUpgrade, BuyAmount::Max),
+            Some((0, d("156.25")))
+        );
+        assert!(!state.can_afford_bulk(Purchase::ProductionUpgrade, BuyAmount::Max));
+        let again = apply(&state, Msg::BuyMany(Purchase::ProductionUpgrade, BuyAmount::Max));
+        assert_eq!(again.upgrade_level, 3);
+
+        let clicks = apply(&state_with(125), Msg::BuyMany(Purchase::ClickUpgrade, BuyAmount::Max));
+        assert_eq!((clicks.click_level, clicks.counter), (2, d("0")));
+    }
+
+    #[test]
+    fn statistics_track_play() {
+        let clock = Rc::new(ManualClock::new(0.0));
+        let env = Env::in_memory(clock.clone());
+        let mut state = state_with(100);
+        state = reducer(&state, Msg::Tick(1500), &env);
+        state = reducer(&state, Msg::UpgradeProduction, &env);
+        state = reducer(&state, Msg::BuyMany(Purchase::ProductionUpgrade, BuyAmount::Ten), &env);
+        state = reducer(&state, Msg::BuyMany(Purchase::Generator(0), BuyAmount::One), &env);
+        state = reducer(&state, Msg::BuyUpgrade("sharper_cursors".to_string()), &env);
+        state = reducer(&state, Msg::Tick(500), &env);
+        assert_eq!(state.stats.upgrades_purchased, 1);
+        assert_eq!(state.stats.online_ms, 2000);
+        assert_eq!(state.stats.highest_production, d("4"));
+
+        state = reducer(&state, Msg::Save, &env);
+        clock.advance(60_000.0);
+        state = reducer(&state, Msg::Load, &env);
+        state = reducer(&state, Msg::Reset, &env);
+        let stats = &state.stats;
+        assert_eq!((stats.saves, stats.loads, stats.resets), (1, 1, 1));
+        assert_eq!(stats.offline_ms, 60_000);
+        assert_eq!(stats.session_started_at, 60_000.0);
+        assert_eq!(stats.highest_production, d("4"));
+    }
+
+    #[test]
+    fn autosave_writes_only_unsaved_changes() {
+        let clock = Rc::new(ManualClock::new(0.0));
+        let env = Env::in_memory(clock.clone());
+        let state = reducer(&state_with(0), Msg::Tick(1000), &env);
+        assert_eq!(state.save_status(), SaveStatus::Pending);
+
+        let saved = reducer(&state, Msg::Autosave, &env);
+        assert_eq!(saved.save_status(), SaveStatus::Saved);
+        assert_eq!(saved.stats.saves, 1);
+        assert_eq!(State::load(&env, 0).unwrap().unwrap().counter, d("1"));
+
+        // Nothing changed, so nothing is written.
+        clock.advance(5_000.0);
+        let idle = reducer(&saved, Msg::Autosave, &env);
+        assert_eq!(idle, saved);
+        let idle = reducer(&idle, Msg::DismissOfflineReport, &env);
+        assert!(!idle.dirty);
+
+        let changed = reducer(&idle, Msg::Tick(1000), &env);
+        let saved = reducer(&changed, Msg::Autosave, &env);
+        assert_eq!(saved.stats.saves, 2);
+        assert_eq!(saved.last_saved_at, Some(5_000.0));
+    }
+
+    #[test]
+    fn failed_autosaves_stay_pending() {
+        let env = Env {
+            clock: Box::new(ManualClock::new(0.0)),
+            store: Box::new(FullStore),
+        };
+        let state = reducer(&state_with(0), Msg::Tick(1000), &env);
+        let failed = reducer(&state, Msg::Autosave, &env);
+        assert!(matches!(failed.save_status(), SaveStatus::Error(_)));
+        assert!(failed.dirty);
+    }
+
+    #[test]
+    fn late_write_failures_are_retried_by_the_next_autosave() {
+        let env = Env::in_memory(ManualClock::new(0.0));
+        let saved = reducer(&state_with(0), Msg::Save, &env);
+        let failed = reducer(&saved, Msg::WriteFailed("QuotaExceededError".to_string()), &env);
+        assert_eq!(failed.save_status(), SaveStatus::Error(Error::Other("QuotaExceededError".to_string())));
+
+        let retried = reducer(&failed, Msg::Autosave, &env);
+        assert_eq!(retried.save_status(), SaveStatus::Saved);
+        assert_eq!(retried.stats.saves, 2);
+    }
+
+    #[test]
+    fn autosave_interval_is_a_setting() {
+        let state = apply(&state_with(0), Msg::SetAutosaveInterval(30));
+        assert_eq!(state.autosave_seconds, 30);
+        assert_eq!(apply(&state, Msg::Reset).autosave_seconds, 30);
+        assert_eq!(apply(&state, Msg::SetAutosaveInterval(0)).autosave_seconds, 1);
+    }
+
+    #[test]
+    fn earnings_between_ticks_match_the_next_tick() {
+        let mut state = state_with(5);
+        state.generators[0] = 3;
+        assert_eq!(state.earned_in(0), Number::zero());
+        assert_eq!(state.earned_in(500), d("1.5"));
+        assert_eq!(&state.counter + state.earned_in(1234), apply(&state, Msg::Tick(1234)).counter);
+    }
+
+    #[test]
+    fn ticks_count_towards_lifetime_earnings() {
+        let state = apply(&state_with(30), Msg::UpgradeProduction);
+        let state = apply(&state, Msg::Tick(1000));
+        assert_eq!(state.counter, d("22"));
+        assert_eq!(state.lifetime_earned, d("32"));
+    }
+
+    #[test]
+    fn reset_can_be_undone_for_a_while() {
+        let clock = Rc::new(ManualClock::new(0.0));
+        let env = Env::in_memory(clock.clone());
+        let mut state = state_with(500);
+        state.generators[1] = 2;
+        let reset = reducer(&state, Msg::Reset, &env);
+        assert_eq!(reset.counter, Number::zero());
+        assert_eq!(reset.undo.as_ref().unwrap().action, UndoAction::Reset);
+
+        clock.advance(UNDO_WINDOW_MS - 1.0);
+        let ticked = reducer(&reset, Msg::Tick(1000), &env);
+        let undone = reducer(&ticked, Msg::Undo, &env);
+        assert_eq!(undone.counter, d("500"));
+        assert_eq!(undone.generators, state.generators);
+        assert_eq!(undone.stats.resets, 0);
+        assert!(undone.undo.is_none());
+        assert!(undone.dirty);
+
+        // Once the window closes the snapshot is dropped.
+        clock.advance(1.0);
+        let expired = reducer(&reset, Msg::Tick(1000), &env);
+        assert!(expired.undo.is_none());
+        assert_eq!(reducer(&reset, Msg::Undo, &env).counter, Number::zero());
+    }
+
+    #[test]
+    fn load_and_import_can_be_undone() {
+        let env = Env::in_memory(ManualClock::new(0.0));
+        let saved = reducer(&state_with(10), Msg::Save, &env);
+        let played = State {
+            counter: d("70"),
+            ..saved.clone()
+        };
+        let loaded = reducer(&played, Msg::Load, &env);
+        assert_eq!(loaded.counter, d("10"));
+        assert_eq!(reducer(&loaded, Msg::Undo, &env).counter, d("70"));
+
+        let imported = reducer(&played, Msg::Import(state_with(3).export(0.0)), &env);
+        assert_eq!(imported.undo.as_ref().unwrap().action, UndoAction::Import);
+        assert_eq!(reducer(&imported, Msg::Undo, &env).counter, d("70"));
+
+        // A rejected import replaces nothing, so there is nothing to undo.
+        let rejected = reducer(&played, Msg::Import("IG1.garbage".to_string()), &env);
+        assert!(rejected.undo.is_none());
+        assert!(reducer(&loaded, Msg::DismissUndo, &env).undo.is_none());
+    }
+}
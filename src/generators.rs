@@ -0,0 +1,38 @@
+use crate::cost::CostCurve;
+use crate::locale::Text;
+use crate::number::Number;
+
+/// A tier of producer the player can buy any number of.
+pub struct Generator {
+    pub name: Text,
+    /// Counter produced per second by each owned unit.
+    pub base_output: u32,
+    pub base_cost: u32,
+    /// Price growth per owned unit, as `cost_num / cost_den`.
+    pub cost_num: u32,
+    pub cost_den: u32,
+}
+
+impl Generator {
+    pub fn cost_curve(&self) -> CostCurve {
+        CostCurve::Geometric {
+            base: Number::from(self.base_cost),
+            ratio: Number::from_ratio(self.cost_num.into(), self.cost_den.into()),
+        }
+    }
+}
+
+pub const GENERATORS: &[Generator] = &[
+    Generator { name: Text::GeneratorCursor, base_output: 1, base_cost: 10, cost_num: 115, cost_den: 100 },
+    Generator { name: Text::GeneratorFarm, base_output: 8, base_cost: 100, cost_num: 115, cost_den: 100 },
+    Generator { name: Text::GeneratorFactory, base_output: 47, base_cost: 1_100, cost_num: 115, cost_den: 100 },
+    Generator { name: Text::GeneratorMine, base_output: 260, base_cost: 12_000, cost_num: 115, cost_den: 100 },
+    Generator { name: Text::GeneratorBank, base_output: 1_400, base_cost: 130_000, cost_num: 115, cost_den: 100 },
+];
+
+/// Owned count per entry of `GENERATORS`; a fresh game starts with one cursor.
+pub fn starting_generators() -> Vec<u32> {
+    let mut counts = vec![0; GENERATORS.len()];
+    counts[0] = 1;
+    counts
+}
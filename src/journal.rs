@@ -0,0 +1,300 @@
+//! A record of every message the game was sent, so a session can be played
+//! back exactly. The journal starts from a checkpoint, the game and the
+//! storage it reads, and lists each message with the clock reading it was
+//! reduced at. Replaying the messages against the checkpoint on a clock that
+//! repeats those readings gives back the same game, which is what lets a
+//! player's exported replay be re-run natively in a test.
+//!
+//! Only what the game saves is checkpointed: an export loses the click rate
+//! limit and any pending undo from before its checkpoint.
+
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::backups;
+use crate::env::{Clock, Env, ManualClock, MemoryStore, SaveStore};
+use crate::game::{reducer, Msg, State};
+use crate::save_code;
+use crate::slots;
+
+/// Layout of exported replays, so a future one can be told apart.
+pub const REPLAY_VERSION: u32 = 1;
+
+/// Messages kept before the journal starts over from a new checkpoint.
+/// The game ticks once a second, so this covers a few hours of play.
+pub const MAX_ENTRIES: usize = 20_000;
+
+/// Both clock readings the reducer may take.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Reading {
+    pub now: f64,
+    pub monotonic: f64,
+}
+
+/// The clock a journaled game runs on. While a message is reduced it is held
+/// at the reading recorded for that message.
+pub struct JournalClock {
+    clock: Box<dyn Clock>,
+    held: Cell<Option<Reading>>,
+}
+
+impl JournalClock {
+    pub fn new(clock: impl Clock + 'static) -> Self {
+        Self {
+            clock: Box::new(clock),
+            held: Cell::new(None),
+        }
+    }
+
+    pub fn read(&self) -> Reading {
+        Reading {
+            now: self.now(),
+            monotonic: self.monotonic(),
+        }
+    }
+
+    fn hold(&self, reading: Option<Reading>) {
+        self.held.set(reading);
+    }
+}
+
+impl Clock for JournalClock {
+    fn now(&self) -> f64 {
+        self.held.get().map_or_else(|| self.clock.now(), |held| held.now)
+    }
+
+    fn monotonic(&self) -> f64 {
+        self.held.get().map_or_else(|| self.clock.monotonic(), |held| held.monotonic)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub reading: Reading,
+    pub msg: Msg,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Journal {
+    checkpoint: State,
+    /// What the storage keys the game reads held at the checkpoint.
+    store: BTreeMap<String, String>,
+    entries: Vec<Entry>,
+}
+
+impl Journal {
+    /// Start journaling `state`, as it is now.
+    pub fn start(state: &State, env: &Env) -> Self {
+        let store = store_keys(state.slot)
+            .into_iter()
+            .filter_map(|key| {
+                let value = env.store.get(&key).ok().flatten()?;
+                Some((key, value))
+            })
+            .collect();
+        Self {
+            checkpoint: state.clone(),
+            store,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Reduce `msg` like `reducer` and journal it. `clock` must be the clock
+    /// of `env`.
+    pub fn dispatch(&mut self, state: &State, msg: Msg, env: &Env, clock: &JournalClock) -> State {
+        if self.entries.len() >= MAX_ENTRIES {
+            *self = Journal::start(state, env);
+        }
+        let reading = clock.read();
+        self.entries.push(Entry {
+            reading,
+            msg: msg.clone(),
+        });
+        clock.hold(Some(reading));
+        let next = reducer(state, msg, env);
+        clock.hold(None);
+        next
+    }
+
+    /// Play every message back from the checkpoint.
+    pub fn replay(&self) -> State {
+        let clock = Rc::new(JournalClock::new(ManualClock::default()));
+        let store = MemoryStore::default();
+        for (key, value) in &self.store {
+            store.set(key, value).expect("memory stores cannot fail");
+        }
+        let env = Env {
+            clock: Box::new(clock.clone()),
+            store: Box::new(store),
+        };
+        self.entries.iter().fold(self.checkpoint.clone(), |state, entry| {
+            clock.hold(Some(entry.reading));
+            reducer(&state, entry.msg.clone(), &env)
+        })
+    }
+
+    /// A replay code for a bug report, ending in `current`.
+    pub fn export(&self, current: &State) -> String {
+        let replay = Replay {
+            version: REPLAY_VERSION,
+            slot: self.checkpoint.slot,
+            checkpoint: serde_json::to_value(&self.checkpoint).expect("State always serializes"),
+            store: self.store.clone(),
+            entries: self.entries.clone(),
+            result: serde_json::to_value(current).expect("State always serializes"),
+        };
+        let json = serde_json::to_string(&replay).expect("Replay always serializes");
+        save_code::encode(&json)
+    }
+}
+
+/// A journal as exported, with the game it ended in.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub slot: usize,
+    /// The checkpoint, as a save.
+    pub checkpoint: Value,
+    pub store: BTreeMap<String, String>,
+    pub entries: Vec<Entry>,
+    /// The game when the replay was exported, as a save.
+    pub result: Value,
+}
+
+impl Replay {
+    pub fn import(code: &str) -> Result<Self, String> {
+        let json = save_code::decode(code).map_err(|e| e.to_string())?;
+        let replay: Replay = serde_json::from_str(&json).map_err(|e| format!("Replay is invalid: {}", e))?;
+        if replay.version != REPLAY_VERSION {
+            return Err(format!("Unknown replay version {}", replay.version));
+        }
+        Ok(replay)
+    }
+
+    pub fn journal(&self) -> Result<Journal, String> {
+        if self.slot >= slots::SLOT_COUNT {
+            return Err(format!("Replay is for unknown slot {}", self.slot));
+        }
+        let checkpoint = State::from_save(self.checkpoint.clone())?;
+        Ok(Journal {
+            checkpoint: State {
+                slot: self.slot,
+                ..checkpoint
+            },
+            store: self.store.clone(),
+            entries: self.entries.clone(),
+        })
+    }
+
+    /// Replay the journal and check it ends where the player's game did.
+    pub fn verify(&self) -> Result<State, String> {
+        let replayed = self.journal()?.replay();
+        let result = serde_json::to_value(&replayed).map_err(|e| e.to_string())?;
+        if result != self.result {
+            return Err("Replay ended in a different game".to_string());
+        }
+        Ok(replayed)
+    }
+}
+
+/// Every storage key the reducer reads for a game in `slot`.
+fn store_keys(slot: usize) -> Vec<String> {
+    vec![
+        slots::slot_key(slot),
+        slots::INDEX_KEY.to_string(),
+        backups::backup_key(slot),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{BuyAmount, Purchase};
+    use crate::number::Number;
+
+    fn journaled_env() -> (Env, Rc<JournalClock>, Rc<ManualClock>) {
+        let time = Rc::new(ManualClock::new(1_000.0));
+        let clock = Rc::new(JournalClock::new(time.clone()));
+        (Env::in_memory(clock.clone()), clock, time)
+    }
+
+    fn play(journal: &mut Journal, state: State, env: &Env, clock: &JournalClock, time: &ManualClock) -> State {
+        let msgs = [
+            Msg::Tick(1000),
+            Msg::Click,
+            Msg::Click,
+            Msg::Save,
+            Msg::BuyMany(Purchase::Generator(0), BuyAmount::Max),
+            Msg::Tick(30_000),
+            Msg::Load,
+            Msg::Undo,
+            Msg::Reset,
+        ];
+        msgs.into_iter().fold(state, |state, msg| {
+            time.advance(700.0);
+            journal.dispatch(&state, msg, env, clock)
+        })
+    }
+
+    #[test]
+    fn replay_rebuilds_the_game() {
+        let (env, clock, time) = journaled_env();
+        let start = State {
+            counter: Number::from(40u32),
+            ..State::new(0.0)
+        };
+        let start = reducer(&start, Msg::Save, &env);
+        let mut journal = Journal::start(&start, &env);
+        let end = play(&mut journal, start, &env, &clock, &time);
+
+        assert_eq!(journal.entries().len(), 9);
+        assert_eq!(journal.entries()[0].reading.now, 1_700.0);
+        assert_eq!(journal.replay(), end);
+    }
+
+    #[test]
+    fn exported_replays_verify() {
+        let (env, clock, time) = journaled_env();
+        let start = reducer(&State::new(0.0), Msg::Save, &env);
+        let mut journal = Journal::start(&start, &env);
+        let end = play(&mut journal, start, &env, &clock, &time);
+
+        let replay = Replay::import(&journal.export(&end)).unwrap();
+        assert_eq!(replay.verify().unwrap().counter, end.counter);
+
+        let tampered = Replay {
+            entries: replay.entries[1..].to_vec(),
+            ..replay.clone()
+        };
+        assert!(tampered.verify().is_err());
+
+        let unknown_slot = Replay {
+            slot: slots::SLOT_COUNT,
+            ..replay
+        };
+        assert_eq!(
+            unknown_slot.verify().map(|_| ()),
+            Err(format!("Replay is for unknown slot {}", slots::SLOT_COUNT))
+        );
+    }
+
+    #[test]
+    fn long_journals_start_over() {
+        let (env, clock, _time) = journaled_env();
+        let mut journal = Journal::start(&State::new(0.0), &env);
+        let mut state = State::new(0.0);
+        for _ in 0..MAX_ENTRIES + 5 {
+            state = journal.dispatch(&state, Msg::Tick(1000), &env, &clock);
+        }
+        assert_eq!(journal.entries().len(), 5);
+        assert_eq!(journal.replay(), state);
+    }
+}
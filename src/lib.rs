@@ -0,0 +1,28 @@
+//! Rules of the idle game, kept free of browser APIs so the whole simulation
+//! runs and is tested natively. The Yew front end (`src/main.rs`) is a thin
+//! shell that supplies a real clock and storage through [`env::Env`].
+
+pub mod achievements;
+pub mod autosave;
+pub mod backups;
+pub mod big_float;
+pub mod click;
+pub mod cost;
+pub mod decimal;
+pub mod env;
+pub mod error;
+pub mod format;
+pub mod game;
+pub mod generators;
+pub mod journal;
+pub mod locale;
+pub mod notation;
+pub mod number;
+pub mod save;
+pub mod save_code;
+pub mod slots;
+pub mod stats;
+pub mod sync;
+pub mod tick;
+pub mod undo;
+pub mod upgrades;
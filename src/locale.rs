@@ -0,0 +1,1118 @@
+//! Languages the game is shown in: separators, number words and UI strings.
+//! Anything a locale does not translate falls back to English.
+
+use serde::{Deserialize, Serialize};
+
+use crate::env::SaveStore;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+    Fr,
+    Es,
+}
+
+pub const LOCALES: &[Locale] = &[Locale::En, Locale::De, Locale::Fr, Locale::Es];
+
+/// The locale is a preference of whoever uses this browser rather than of a
+/// save, so it lives outside the slots.
+const PREFERENCE_KEY: &str = "idle_game_locale";
+
+/// Text shown in the UI. Strings with `{}` take arguments through
+/// [`Locale::fill`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Text {
+    Title,
+    Counter,
+    ProductionPerSecond,
+    PrestigePoints,
+    PrestigeMultiplier,
+    PrestigeFor,
+    GeneratorOutput,
+    BuyCost,
+    UpgradeCost,
+    UpgradeMaxed,
+    SaveGame,
+    LoadGame,
+    ResetGame,
+    SwitchSlot,
+    Achievements,
+    AchievementBonus,
+    AchievementUnlocked,
+    ExportSave,
+    ImportPlaceholder,
+    ImportSave,
+    WelcomeBack,
+    Continue,
+    OfflineSummary,
+    OfflineCapped,
+    NumberNotation,
+    Language,
+    Storage,
+    LastSaved,
+    Never,
+    JustNow,
+    MinutesAgo,
+    HoursAgo,
+    SlotCounter,
+    SlotDates,
+    NewGame,
+    Play,
+    SlotsError,
+    CreateSlotError,
+    NotationShort,
+    NotationScientific,
+    NotationEngineering,
+    NotationLongNames,
+    NotationLogarithmic,
+    Rate,
+    ClickTarget,
+    ClickUpgradeCost,
+    ClickStats,
+    UpgradeTree,
+    Purchased,
+    Requires,
+    BuyMax,
+    BuyManyCost,
+    UpgradeManyCost,
+    ClickUpgradeManyCost,
+    TabPlay,
+    TabStats,
+    StatLifetimeEarned,
+    StatUpgradesPurchased,
+    StatTimeOnline,
+    StatTimeOffline,
+    StatHighestProduction,
+    StatSaves,
+    StatLoads,
+    StatResets,
+    StatSessionLength,
+    AutosaveEvery,
+    Seconds,
+    SaveStatusSaved,
+    SaveStatusPending,
+    SaveStatusError,
+    TabBackups,
+    BackupRecent,
+    BackupDaily,
+    BackupSummary,
+    RestoreBackup,
+    NoBackups,
+    BackupError,
+    ConfirmTitle,
+    ConfirmReset,
+    ConfirmLoad,
+    ConfirmImport,
+    ConfirmRestore,
+    Cancel,
+    UndoneReset,
+    UndoneLoad,
+    UndoneImport,
+    UndoneRestore,
+    UndoneSync,
+    Undo,
+    ExportReplay,
+    CloudSync,
+    SyncEndpoint,
+    SyncToken,
+    SyncNow,
+    SyncOff,
+    SyncBusy,
+    SyncUpToDate,
+    SyncPushed,
+    SyncPulled,
+    SyncFailed,
+    SyncConflictTitle,
+    SyncConflict,
+    SyncThisBrowser,
+    SyncServer,
+    SyncLifetime,
+    SyncFurtherAhead,
+    SyncKeepLocal,
+    SyncKeepRemote,
+    MinutesSeconds,
+    HoursMinutes,
+    GeneratorCursor,
+    GeneratorFarm,
+    GeneratorFactory,
+    GeneratorMine,
+    GeneratorBank,
+    AchievementPocketChange,
+    AchievementPocketChangeGoal,
+    AchievementMillionaire,
+    AchievementMillionaireGoal,
+    AchievementIndustrialScale,
+    AchievementIndustrialScaleGoal,
+    AchievementTinkerer,
+    AchievementTinkererGoal,
+    AchievementCollector,
+    AchievementCollectorGoal,
+    AchievementGoodNightsSleep,
+    AchievementGoodNightsSleepGoal,
+    AchievementBornAgain,
+    AchievementBornAgainGoal,
+    UpgradeSharperCursors,
+    UpgradeSharperCursorsEffect,
+    UpgradeFertileFields,
+    UpgradeFertileFieldsEffect,
+    UpgradeAssemblyLines,
+    UpgradeAssemblyLinesEffect,
+    UpgradeDeepShafts,
+    UpgradeDeepShaftsEffect,
+    UpgradeCompoundInterest,
+    UpgradeCompoundInterestEffect,
+    UpgradeSynergy,
+    UpgradeSynergyEffect,
+    UpgradeMassProduction,
+    UpgradeMassProductionEffect,
+    UpgradeStrongFingers,
+    UpgradeStrongFingersEffect,
+    UpgradeIronGrip,
+    UpgradeIronGripEffect,
+    UpgradeNightShift,
+    UpgradeNightShiftEffect,
+    UpgradeAutomation,
+    UpgradeAutomationEffect,
+    ErrorNotASaveCode,
+    ErrorSaveCodeTruncated,
+    ErrorSaveCodeCorrupted,
+    ErrorSaveCodeChecksum,
+    ErrorSaveCodeInvalid,
+    ErrorBackupNotFound,
+    ErrorNothingSaved,
+    ErrorNotSaved,
+    ErrorSyncToken,
+    ErrorSyncStatus,
+    ErrorSyncInvalidSave,
+}
+
+impl Locale {
+    /// BCP 47 language subtag.
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+            Locale::Fr => "fr",
+            Locale::Es => "es",
+        }
+    }
+
+    /// The locale for a language tag such as `de-AT`, matched on its
+    /// language alone.
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let language = tag.split(['-', '_']).next()?.to_ascii_lowercase();
+        LOCALES.iter().copied().find(|locale| locale.tag() == language)
+    }
+
+    /// Name of the language in itself, for the picker.
+    pub fn label(self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::De => "Deutsch",
+            Locale::Fr => "Français",
+            Locale::Es => "Español",
+        }
+    }
+
+    pub fn decimal_separator(self) -> char {
+        match self {
+            Locale::En => '.',
+            Locale::De | Locale::Fr | Locale::Es => ',',
+        }
+    }
+
+    pub fn thousands_separator(self) -> char {
+        match self {
+            Locale::En => ',',
+            Locale::De | Locale::Es => '.',
+            // Narrow no-break space.
+            Locale::Fr => '\u{202f}',
+        }
+    }
+
+    /// Rewrite a plain `1234567.89` style number with this locale's
+    /// separators. Numbers of four digits or fewer are not grouped.
+    pub fn number(self, plain: &str) -> String {
+        let (int, frac) = match plain.split_once('.') {
+            Some((int, frac)) => (int, Some(frac)),
+            None => (plain, None),
+        };
+        let mut out = String::new();
+        if int.len() > 4 {
+            for (i, digit) in int.chars().enumerate() {
+                if i > 0 && (int.len() - i) % 3 == 0 {
+                    out.push(self.thousands_separator());
+                }
+                out.push(digit);
+            }
+        } else {
+            out.push_str(int);
+        }
+        if let Some(frac) = frac {
+            out.push(self.decimal_separator());
+            out.push_str(frac);
+        }
+        out
+    }
+
+    pub fn text(self, text: Text) -> &'static str {
+        lookup(self.translations(), text).unwrap_or_else(|| english(text))
+    }
+
+    /// `text` with each `{}` replaced by the next argument.
+    pub fn fill(self, text: Text, args: &[&str]) -> String {
+        let mut parts = self.text(text).split("{}");
+        let mut out = parts.next().unwrap_or_default().to_string();
+        for (i, part) in parts.enumerate() {
+            out.push_str(args.get(i).copied().unwrap_or_default());
+            out.push_str(part);
+        }
+        out
+    }
+
+    fn translations(self) -> &'static [(Text, &'static str)] {
+        match self {
+            Locale::En => &[],
+            Locale::De => GERMAN,
+            Locale::Fr => FRENCH,
+            Locale::Es => SPANISH,
+        }
+    }
+
+    /// Suffixes for the short notation, indexed by thousands group. Groups
+    /// past the end use the English suffixes.
+    pub(crate) fn short_suffixes(self) -> &'static [&'static str] {
+        match self {
+            Locale::En => &[],
+            Locale::De => &["", " Tsd.", " Mio.", " Mrd.", " Bio.", " Brd.", " Trio.", " Trd."],
+            Locale::Fr => &["", " k", " M", " Md", " Bn", " Bd", " Tn", " Td"],
+            Locale::Es => &["", " mil", " M", " mil M", " B", " mil B", " Tr", " mil Tr"],
+        }
+    }
+
+    /// Singular and plural names of each thousands group from 10^3 on, or
+    /// `None` for the generated English short-scale names. These locales
+    /// count on the long scale, so their names stop where the table does.
+    pub(crate) fn long_names(self) -> Option<&'static [(&'static str, &'static str)]> {
+        match self {
+            Locale::En => None,
+            Locale::De => Some(&[
+                ("Tausend", "Tausend"),
+                ("Million", "Millionen"),
+                ("Milliarde", "Milliarden"),
+                ("Billion", "Billionen"),
+                ("Billiarde", "Billiarden"),
+                ("Trillion", "Trillionen"),
+                ("Trilliarde", "Trilliarden"),
+                ("Quadrillion", "Quadrillionen"),
+                ("Quadrilliarde", "Quadrilliarden"),
+                ("Quintillion", "Quintillionen"),
+                ("Quintilliarde", "Quintilliarden"),
+            ]),
+            Locale::Fr => Some(&[
+                ("mille", "mille"),
+                ("million", "millions"),
+                ("milliard", "milliards"),
+                ("billion", "billions"),
+                ("billiard", "billiards"),
+                ("trillion", "trillions"),
+                ("trilliard", "trilliards"),
+                ("quadrillion", "quadrillions"),
+                ("quadrilliard", "quadrilliards"),
+                ("quintillion", "quintillions"),
+                ("quintilliard", "quintilliards"),
+            ]),
+            Locale::Es => Some(&[
+                ("mil", "mil"),
+                ("millón", "millones"),
+                ("mil millones", "mil millones"),
+                ("billón", "billones"),
+                ("mil billones", "mil billones"),
+                ("trillón", "trillones"),
+                ("mil trillones", "mil trillones"),
+                ("cuatrillón", "cuatrillones"),
+                ("mil cuatrillones", "mil cuatrillones"),
+                ("quintillón", "quintillones"),
+                ("mil quintillones", "mil quintillones"),
+            ]),
+        }
+    }
+
+    /// Whether a plain `1.5` style amount takes the singular of a number
+    /// word. French uses it for everything below two, the others only for
+    /// exactly one.
+    pub(crate) fn is_singular(self, amount: &str) -> bool {
+        let (int, frac) = amount.split_once('.').unwrap_or((amount, ""));
+        match self {
+            Locale::Fr => int == "1" || int == "0",
+            _ => int == "1" && frac.is_empty(),
+        }
+    }
+}
+
+/// The locale saved for this browser, if one was picked.
+pub fn load_preference(store: &dyn SaveStore) -> Result<Option<Locale>, String> {
+    Ok(store.get(PREFERENCE_KEY)?.as_deref().and_then(Locale::from_tag))
+}
+
+pub fn save_preference(store: &dyn SaveStore, locale: Locale) -> Result<(), String> {
+    store.set(PREFERENCE_KEY, locale.tag())
+}
+
+fn lookup(table: &[(Text, &'static str)], text: Text) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(key, _)| *key == text)
+        .map(|(_, translated)| *translated)
+}
+
+fn english(text: Text) -> &'static str {
+    match text {
+        Text::Title => "Idle Game with Big Numbers",
+        Text::Counter => "Counter",
+        Text::ProductionPerSecond => "Production per second",
+        Text::PrestigePoints => "Prestige points",
+        Text::PrestigeMultiplier => "Production x{}%",
+        Text::PrestigeFor => "Prestige for +{} points",
+        Text::GeneratorOutput => "+{} per second each",
+        Text::BuyCost => "Buy - Cost: {}",
+        Text::UpgradeCost => "Upgrade Production (Double) - Cost: {}",
+        Text::UpgradeMaxed => "Upgrade Production (Maxed)",
+        Text::SaveGame => "Save Game",
+        Text::LoadGame => "Load Game",
+        Text::ResetGame => "Reset Game",
+        Text::SwitchSlot => "Switch Slot (playing {})",
+        Text::Achievements => "Achievements ({}/{})",
+        Text::AchievementBonus => "+{}% production",
+        Text::AchievementUnlocked => "Achievement unlocked: {}",
+        Text::ExportSave => "Export Save",
+        Text::ImportPlaceholder => "Paste a save code to import",
+        Text::ImportSave => "Import Save",
+        Text::WelcomeBack => "Welcome back!",
+        Text::Continue => "Continue",
+        Text::OfflineSummary => "While you were away for {} you earned {}.",
+        Text::OfflineCapped => " Offline earnings are capped at {}.",
+        Text::NumberNotation => "Number notation",
+        Text::Language => "Language",
+        Text::Storage => "Saves kept in",
+        Text::LastSaved => "Last saved: {} ({})",
+        Text::Never => "Never",
+        Text::JustNow => "Just now",
+        Text::MinutesAgo => "{} minutes ago",
+        Text::HoursAgo => "{} hours ago",
+        Text::SlotCounter => "Counter: {}",
+        Text::SlotDates => "Created {} - last saved {}",
+        Text::NewGame => "New Game",
+        Text::Play => "Play",
+        Text::SlotsError => "Could not read save slots: {}",
+        Text::CreateSlotError => "Could not create slot: {}",
+        Text::NotationShort => "Short suffixes",
+        Text::NotationScientific => "Scientific",
+        Text::NotationEngineering => "Engineering",
+        Text::NotationLongNames => "Long names",
+        Text::NotationLogarithmic => "Logarithmic",
+        Text::Rate => "+{}/s",
+        Text::ClickTarget => "Click! +{}",
+        Text::ClickUpgradeCost => "Upgrade Click Power (Double) - Cost: {}",
+        Text::ClickStats => "{} clicks earned {}",
+        Text::UpgradeTree => "Upgrade Tree",
+        Text::Purchased => "Purchased",
+        Text::Requires => "Requires {}",
+        Text::BuyMax => "Max",
+        Text::BuyManyCost => "Buy {} - Cost: {}",
+        Text::UpgradeManyCost => "Upgrade Production ({} levels) - Cost: {}",
+        Text::ClickUpgradeManyCost => "Upgrade Click Power ({} levels) - Cost: {}",
+        Text::TabPlay => "Game",
+        Text::TabStats => "Statistics",
+        Text::StatLifetimeEarned => "Lifetime earned",
+        Text::StatUpgradesPurchased => "Upgrades purchased",
+        Text::StatTimeOnline => "Time played",
+        Text::StatTimeOffline => "Time away",
+        Text::StatHighestProduction => "Highest production per second",
+        Text::StatSaves => "Saves",
+        Text::StatLoads => "Loads",
+        Text::StatResets => "Resets",
+        Text::StatSessionLength => "Current session",
+        Text::AutosaveEvery => "Autosave every",
+        Text::Seconds => "{}s",
+        Text::SaveStatusSaved => "All progress saved",
+        Text::SaveStatusPending => "Unsaved changes",
+        Text::SaveStatusError => "Save failed: {}",
+        Text::TabBackups => "Backups",
+        Text::BackupRecent => "Autosave",
+        Text::BackupDaily => "Daily snapshot",
+        Text::BackupSummary => "Counter: {} - Production: {}/s",
+        Text::RestoreBackup => "Restore",
+        Text::NoBackups => "No backups yet. One is kept as the game saves.",
+        Text::BackupError => "Saved, but backups were not updated: {}",
+        Text::ConfirmTitle => "Are you sure?",
+        Text::ConfirmReset => "Reset this game? All progress in this slot will be lost.",
+        Text::ConfirmLoad => "Load the last save? Progress since then will be lost.",
+        Text::ConfirmImport => "Import this save? It will replace the current game.",
+        Text::ConfirmRestore => "Restore this backup? It will replace the current game.",
+        Text::Cancel => "Cancel",
+        Text::UndoneReset => "Game reset.",
+        Text::UndoneLoad => "Save loaded.",
+        Text::UndoneImport => "Save imported.",
+        Text::UndoneRestore => "Backup restored.",
+        Text::UndoneSync => "Replaced with the server's game.",
+        Text::Undo => "Undo ({}s)",
+        Text::ExportReplay => "Export Replay for a Bug Report",
+        Text::CloudSync => "Cloud Sync",
+        Text::SyncEndpoint => "Sync server URL",
+        Text::SyncToken => "Sync token",
+        Text::SyncNow => "Sync Now",
+        Text::SyncOff => "Enter a sync server to keep this slot in sync.",
+        Text::SyncBusy => "Syncing...",
+        Text::SyncUpToDate => "In sync with the server.",
+        Text::SyncPushed => "Sent this game to the server.",
+        Text::SyncPulled => "Took the server's game.",
+        Text::SyncFailed => "Sync failed: {}",
+        Text::SyncConflictTitle => "Two different games",
+        Text::SyncConflict => "This browser and the server both have progress the other hasn't seen. Which game do you want to keep?",
+        Text::SyncThisBrowser => "This browser",
+        Text::SyncServer => "Server",
+        Text::SyncLifetime => "{} earned in total",
+        Text::SyncFurtherAhead => "Further ahead",
+        Text::SyncKeepLocal => "Keep This Browser's",
+        Text::SyncKeepRemote => "Keep the Server's",
+        Text::MinutesSeconds => "{}m {}s",
+        Text::HoursMinutes => "{}h {}m",
+        Text::GeneratorCursor => "Cursor",
+        Text::GeneratorFarm => "Farm",
+        Text::GeneratorFactory => "Factory",
+        Text::GeneratorMine => "Mine",
+        Text::GeneratorBank => "Bank",
+        Text::AchievementPocketChange => "Pocket Change",
+        Text::AchievementPocketChangeGoal => "Have 1K at once",
+        Text::AchievementMillionaire => "Millionaire",
+        Text::AchievementMillionaireGoal => "Have 1M at once",
+        Text::AchievementIndustrialScale => "Industrial Scale",
+        Text::AchievementIndustrialScaleGoal => "Produce 1B per second",
+        Text::AchievementTinkerer => "Tinkerer",
+        Text::AchievementTinkererGoal => "Buy 10 production upgrades",
+        Text::AchievementCollector => "Collector",
+        Text::AchievementCollectorGoal => "Own 100 generators",
+        Text::AchievementGoodNightsSleep => "Good Night's Sleep",
+        Text::AchievementGoodNightsSleepGoal => "Come back after 8 hours away",
+        Text::AchievementBornAgain => "Born Again",
+        Text::AchievementBornAgainGoal => "Earn a prestige point",
+        Text::UpgradeSharperCursors => "Sharper Cursors",
+        Text::UpgradeSharperCursorsEffect => "Cursors produce twice as much",
+        Text::UpgradeFertileFields => "Fertile Fields",
+        Text::UpgradeFertileFieldsEffect => "Farms produce twice as much",
+        Text::UpgradeAssemblyLines => "Assembly Lines",
+        Text::UpgradeAssemblyLinesEffect => "Factories produce twice as much",
+        Text::UpgradeDeepShafts => "Deep Shafts",
+        Text::UpgradeDeepShaftsEffect => "Mines produce twice as much",
+        Text::UpgradeCompoundInterest => "Compound Interest",
+        Text::UpgradeCompoundInterestEffect => "Banks produce twice as much",
+        Text::UpgradeSynergy => "Synergy",
+        Text::UpgradeSynergyEffect => "All production +50%",
+        Text::UpgradeMassProduction => "Mass Production",
+        Text::UpgradeMassProductionEffect => "All production x2",
+        Text::UpgradeStrongFingers => "Strong Fingers",
+        Text::UpgradeStrongFingersEffect => "Clicks are worth three times as much",
+        Text::UpgradeIronGrip => "Iron Grip",
+        Text::UpgradeIronGripEffect => "Clicks are worth five times as much",
+        Text::UpgradeNightShift => "Night Shift",
+        Text::UpgradeNightShiftEffect => "+15% offline efficiency",
+        Text::UpgradeAutomation => "Automation",
+        Text::UpgradeAutomationEffect => "+25% offline efficiency",
+        Text::ErrorNotASaveCode => "Not a save code",
+        Text::ErrorSaveCodeTruncated => "Save code is truncated",
+        Text::ErrorSaveCodeCorrupted => "Save code is corrupted",
+        Text::ErrorSaveCodeChecksum => "Save code checksum does not match",
+        Text::ErrorSaveCodeInvalid => "Save code is invalid: {}",
+        Text::ErrorBackupNotFound => "Backup not found",
+        Text::ErrorNothingSaved => "Nothing is saved in this slot",
+        Text::ErrorNotSaved => "The game could not be saved",
+        Text::ErrorSyncToken => "Sync server rejected the token",
+        Text::ErrorSyncStatus => "Sync server answered {}",
+        Text::ErrorSyncInvalidSave => "Sync server sent an invalid save: {}",
+    }
+}
+
+const GERMAN: &[(Text, &str)] = &[
+    (Text::Title, "Idle-Spiel mit großen Zahlen"),
+    (Text::Counter, "Zähler"),
+    (Text::ProductionPerSecond, "Produktion pro Sekunde"),
+    (Text::PrestigePoints, "Prestigepunkte"),
+    (Text::PrestigeMultiplier, "Produktion x{}%"),
+    (Text::PrestigeFor, "Prestige für +{} Punkte"),
+    (Text::GeneratorOutput, "+{} pro Sekunde je Stück"),
+    (Text::BuyCost, "Kaufen - Kosten: {}"),
+    (Text::UpgradeCost, "Produktion verbessern (verdoppeln) - Kosten: {}"),
+    (Text::UpgradeMaxed, "Produktion verbessern (maximal)"),
+    (Text::SaveGame, "Spiel speichern"),
+    (Text::LoadGame, "Spiel laden"),
+    (Text::ResetGame, "Spiel zurücksetzen"),
+    (Text::SwitchSlot, "Spielstand wechseln (aktuell {})"),
+    (Text::Achievements, "Erfolge ({}/{})"),
+    (Text::AchievementBonus, "+{}% Produktion"),
+    (Text::AchievementUnlocked, "Erfolg freigeschaltet: {}"),
+    (Text::ExportSave, "Spielstand exportieren"),
+    (Text::ImportPlaceholder, "Spielstand-Code zum Importieren einfügen"),
+    (Text::ImportSave, "Spielstand importieren"),
+    (Text::WelcomeBack, "Willkommen zurück!"),
+    (Text::Continue, "Weiter"),
+    (Text::OfflineSummary, "Während deiner Abwesenheit von {} hast du {} verdient."),
+    (Text::OfflineCapped, " Offline-Einnahmen sind auf {} begrenzt."),
+    (Text::NumberNotation, "Zahlenschreibweise"),
+    (Text::Language, "Sprache"),
+    (Text::Storage, "Spielstände in"),
+    (Text::LastSaved, "Zuletzt gespeichert: {} ({})"),
+    (Text::Never, "Nie"),
+    (Text::JustNow, "Gerade eben"),
+    (Text::MinutesAgo, "vor {} Minuten"),
+    (Text::HoursAgo, "vor {} Stunden"),
+    (Text::SlotCounter, "Zähler: {}"),
+    (Text::SlotDates, "Erstellt {} - zuletzt gespeichert {}"),
+    (Text::NewGame, "Neues Spiel"),
+    (Text::Play, "Spielen"),
+    (Text::SlotsError, "Spielstände konnten nicht gelesen werden: {}"),
+    (Text::CreateSlotError, "Spielstand konnte nicht angelegt werden: {}"),
+    (Text::NotationShort, "Kurze Suffixe"),
+    (Text::NotationScientific, "Wissenschaftlich"),
+    (Text::NotationEngineering, "Technisch"),
+    (Text::NotationLongNames, "Zahlwörter"),
+    (Text::NotationLogarithmic, "Logarithmisch"),
+    (Text::Rate, "+{}/s"),
+    (Text::ClickTarget, "Klick! +{}"),
+    (Text::ClickUpgradeCost, "Klickkraft verbessern (verdoppeln) - Kosten: {}"),
+    (Text::ClickStats, "{} Klicks haben {} eingebracht"),
+    (Text::UpgradeTree, "Upgrade-Baum"),
+    (Text::Purchased, "Gekauft"),
+    (Text::Requires, "Benötigt {}"),
+    (Text::BuyMax, "Max"),
+    (Text::BuyManyCost, "{} kaufen - Kosten: {}"),
+    (Text::UpgradeManyCost, "Produktion verbessern ({} Stufen) - Kosten: {}"),
+    (Text::ClickUpgradeManyCost, "Klickkraft verbessern ({} Stufen) - Kosten: {}"),
+    (Text::TabPlay, "Spiel"),
+    (Text::TabStats, "Statistiken"),
+    (Text::StatLifetimeEarned, "Insgesamt verdient"),
+    (Text::StatUpgradesPurchased, "Gekaufte Verbesserungen"),
+    (Text::StatTimeOnline, "Spielzeit"),
+    (Text::StatTimeOffline, "Abwesenheit"),
+    (Text::StatHighestProduction, "Höchste Produktion pro Sekunde"),
+    (Text::StatSaves, "Speicherungen"),
+    (Text::StatLoads, "Ladevorgänge"),
+    (Text::StatResets, "Zurücksetzungen"),
+    (Text::StatSessionLength, "Aktuelle Sitzung"),
+    (Text::AutosaveEvery, "Automatisch speichern alle"),
+    (Text::Seconds, "{} s"),
+    (Text::SaveStatusSaved, "Alles gespeichert"),
+    (Text::SaveStatusPending, "Ungespeicherte Änderungen"),
+    (Text::SaveStatusError, "Speichern fehlgeschlagen: {}"),
+    (Text::TabBackups, "Sicherungen"),
+    (Text::BackupRecent, "Automatische Speicherung"),
+    (Text::BackupDaily, "Tägliche Sicherung"),
+    (Text::BackupSummary, "Zähler: {} - Produktion: {}/s"),
+    (Text::RestoreBackup, "Wiederherstellen"),
+    (Text::NoBackups, "Noch keine Sicherungen. Beim Speichern wird eine angelegt."),
+    (Text::BackupError, "Gespeichert, aber die Sicherungen wurden nicht aktualisiert: {}"),
+    (Text::ConfirmTitle, "Bist du sicher?"),
+    (Text::ConfirmReset, "Dieses Spiel zurücksetzen? Der gesamte Fortschritt in diesem Platz geht verloren."),
+    (Text::ConfirmLoad, "Den letzten Spielstand laden? Der Fortschritt seitdem geht verloren."),
+    (Text::ConfirmImport, "Diesen Spielstand importieren? Er ersetzt das aktuelle Spiel."),
+    (Text::ConfirmRestore, "Diese Sicherung wiederherstellen? Sie ersetzt das aktuelle Spiel."),
+    (Text::Cancel, "Abbrechen"),
+    (Text::UndoneReset, "Spiel zurückgesetzt."),
+    (Text::UndoneLoad, "Spielstand geladen."),
+    (Text::UndoneImport, "Spielstand importiert."),
+    (Text::UndoneRestore, "Sicherung wiederhergestellt."),
+    (Text::UndoneSync, "Durch das Spiel vom Server ersetzt."),
+    (Text::Undo, "Rückgängig ({} s)"),
+    (Text::ExportReplay, "Wiederholung für einen Fehlerbericht exportieren"),
+    (Text::CloudSync, "Cloud-Synchronisation"),
+    (Text::SyncEndpoint, "URL des Sync-Servers"),
+    (Text::SyncToken, "Sync-Token"),
+    (Text::SyncNow, "Jetzt synchronisieren"),
+    (Text::SyncOff, "Gib einen Sync-Server an, um diesen Platz abzugleichen."),
+    (Text::SyncBusy, "Synchronisiere..."),
+    (Text::SyncUpToDate, "Mit dem Server abgeglichen."),
+    (Text::SyncPushed, "Dieses Spiel wurde an den Server gesendet."),
+    (Text::SyncPulled, "Das Spiel vom Server wurde übernommen."),
+    (Text::SyncFailed, "Synchronisation fehlgeschlagen: {}"),
+    (Text::SyncConflictTitle, "Zwei verschiedene Spiele"),
+    (Text::SyncConflict, "Dieser Browser und der Server haben jeweils Fortschritt, den der andere nicht kennt. Welches Spiel möchtest du behalten?"),
+    (Text::SyncThisBrowser, "Dieser Browser"),
+    (Text::SyncServer, "Server"),
+    (Text::SyncLifetime, "Insgesamt {} verdient"),
+    (Text::SyncFurtherAhead, "Weiter fortgeschritten"),
+    (Text::SyncKeepLocal, "Diesen Browser behalten"),
+    (Text::SyncKeepRemote, "Server behalten"),
+    (Text::MinutesSeconds, "{} Min. {} s"),
+    (Text::HoursMinutes, "{} Std. {} Min."),
+    (Text::GeneratorCursor, "Cursor"),
+    (Text::GeneratorFarm, "Bauernhof"),
+    (Text::GeneratorFactory, "Fabrik"),
+    (Text::GeneratorMine, "Mine"),
+    (Text::GeneratorBank, "Bank"),
+    (Text::AchievementPocketChange, "Kleingeld"),
+    (Text::AchievementPocketChangeGoal, "Habe 1 Tsd. auf einmal"),
+    (Text::AchievementMillionaire, "Millionär"),
+    (Text::AchievementMillionaireGoal, "Habe 1 Mio. auf einmal"),
+    (Text::AchievementIndustrialScale, "Industrieller Maßstab"),
+    (Text::AchievementIndustrialScaleGoal, "Produziere 1 Mrd. pro Sekunde"),
+    (Text::AchievementTinkerer, "Bastler"),
+    (Text::AchievementTinkererGoal, "Kaufe 10 Produktionsverbesserungen"),
+    (Text::AchievementCollector, "Sammler"),
+    (Text::AchievementCollectorGoal, "Besitze 100 Generatoren"),
+    (Text::AchievementGoodNightsSleep, "Gut geschlafen"),
+    (Text::AchievementGoodNightsSleepGoal, "Komm nach 8 Stunden Abwesenheit zurück"),
+    (Text::AchievementBornAgain, "Wiedergeboren"),
+    (Text::AchievementBornAgainGoal, "Verdiene einen Prestigepunkt"),
+    (Text::UpgradeSharperCursors, "Schärfere Cursor"),
+    (Text::UpgradeSharperCursorsEffect, "Cursor produzieren doppelt so viel"),
+    (Text::UpgradeFertileFields, "Fruchtbare Felder"),
+    (Text::UpgradeFertileFieldsEffect, "Bauernhöfe produzieren doppelt so viel"),
+    (Text::UpgradeAssemblyLines, "Fließbänder"),
+    (Text::UpgradeAssemblyLinesEffect, "Fabriken produzieren doppelt so viel"),
+    (Text::UpgradeDeepShafts, "Tiefe Schächte"),
+    (Text::UpgradeDeepShaftsEffect, "Minen produzieren doppelt so viel"),
+    (Text::UpgradeCompoundInterest, "Zinseszins"),
+    (Text::UpgradeCompoundInterestEffect, "Banken produzieren doppelt so viel"),
+    (Text::UpgradeSynergy, "Synergie"),
+    (Text::UpgradeSynergyEffect, "Gesamte Produktion +50%"),
+    (Text::UpgradeMassProduction, "Massenproduktion"),
+    (Text::UpgradeMassProductionEffect, "Gesamte Produktion x2"),
+    (Text::UpgradeStrongFingers, "Starke Finger"),
+    (Text::UpgradeStrongFingersEffect, "Klicks sind dreimal so viel wert"),
+    (Text::UpgradeIronGrip, "Eiserner Griff"),
+    (Text::UpgradeIronGripEffect, "Klicks sind fünfmal so viel wert"),
+    (Text::UpgradeNightShift, "Nachtschicht"),
+    (Text::UpgradeNightShiftEffect, "+15% Offline-Effizienz"),
+    (Text::UpgradeAutomation, "Automatisierung"),
+    (Text::UpgradeAutomationEffect, "+25% Offline-Effizienz"),
+    (Text::ErrorNotASaveCode, "Kein Spielstand-Code"),
+    (Text::ErrorSaveCodeTruncated, "Der Spielstand-Code ist unvollständig"),
+    (Text::ErrorSaveCodeCorrupted, "Der Spielstand-Code ist beschädigt"),
+    (Text::ErrorSaveCodeChecksum, "Die Prüfsumme des Spielstand-Codes stimmt nicht"),
+    (Text::ErrorSaveCodeInvalid, "Der Spielstand-Code ist ungültig: {}"),
+    (Text::ErrorBackupNotFound, "Sicherung nicht gefunden"),
+    (Text::ErrorNothingSaved, "In diesem Spielstand ist nichts gespeichert"),
+    (Text::ErrorNotSaved, "Das Spiel konnte nicht gespeichert werden"),
+    (Text::ErrorSyncToken, "Der Sync-Server hat das Token abgelehnt"),
+    (Text::ErrorSyncStatus, "Der Sync-Server antwortete {}"),
+    (Text::ErrorSyncInvalidSave, "Der Sync-Server hat einen ungültigen Spielstand geschickt: {}"),
+];
+
+const FRENCH: &[(Text, &str)] = &[
+    (Text::Title, "Jeu incrémental aux grands nombres"),
+    (Text::Counter, "Compteur"),
+    (Text::ProductionPerSecond, "Production par seconde"),
+    (Text::PrestigePoints, "Points de prestige"),
+    (Text::PrestigeMultiplier, "Production x{} %"),
+    (Text::PrestigeFor, "Prestige pour +{} points"),
+    (Text::GeneratorOutput, "+{} par seconde chacun"),
+    (Text::BuyCost, "Acheter - Coût : {}"),
+    (Text::UpgradeCost, "Améliorer la production (double) - Coût : {}"),
+    (Text::UpgradeMaxed, "Améliorer la production (maximum)"),
+    (Text::SaveGame, "Sauvegarder"),
+    (Text::LoadGame, "Charger"),
+    (Text::ResetGame, "Réinitialiser"),
+    (Text::SwitchSlot, "Changer d'emplacement (en cours : {})"),
+    (Text::Achievements, "Succès ({}/{})"),
+    (Text::AchievementBonus, "+{} % de production"),
+    (Text::AchievementUnlocked, "Succès débloqué : {}"),
+    (Text::ExportSave, "Exporter la sauvegarde"),
+    (Text::ImportPlaceholder, "Collez un code de sauvegarde à importer"),
+    (Text::ImportSave, "Importer la sauvegarde"),
+    (Text::WelcomeBack, "Bon retour !"),
+    (Text::Continue, "Continuer"),
+    (Text::OfflineSummary, "Pendant votre absence de {}, vous avez gagné {}."),
+    (Text::OfflineCapped, " Les gains hors ligne sont plafonnés à {}."),
+    (Text::NumberNotation, "Notation des nombres"),
+    (Text::Language, "Langue"),
+    (Text::Storage, "Sauvegardes dans"),
+    (Text::LastSaved, "Dernière sauvegarde : {} ({})"),
+    (Text::Never, "Jamais"),
+    (Text::JustNow, "À l'instant"),
+    (Text::MinutesAgo, "il y a {} minutes"),
+    (Text::HoursAgo, "il y a {} heures"),
+    (Text::SlotCounter, "Compteur : {}"),
+    (Text::SlotDates, "Créé le {} - dernière sauvegarde {}"),
+    (Text::NewGame, "Nouvelle partie"),
+    (Text::Play, "Jouer"),
+    (Text::SlotsError, "Impossible de lire les emplacements : {}"),
+    (Text::CreateSlotError, "Impossible de créer l'emplacement : {}"),
+    (Text::NotationShort, "Suffixes courts"),
+    (Text::NotationScientific, "Scientifique"),
+    (Text::NotationEngineering, "Ingénieur"),
+    (Text::NotationLongNames, "Noms longs"),
+    (Text::NotationLogarithmic, "Logarithmique"),
+    (Text::Rate, "+{} /s"),
+    (Text::ClickTarget, "Clic ! +{}"),
+    (Text::ClickUpgradeCost, "Améliorer la puissance de clic (double) - Coût : {}"),
+    (Text::ClickStats, "{} clics ont rapporté {}"),
+    (Text::UpgradeTree, "Arbre d'améliorations"),
+    (Text::Purchased, "Acheté"),
+    (Text::Requires, "Nécessite {}"),
+    (Text::BuyMax, "Max"),
+    (Text::BuyManyCost, "Acheter {} - Coût : {}"),
+    (Text::UpgradeManyCost, "Améliorer la production ({} niveaux) - Coût : {}"),
+    (Text::ClickUpgradeManyCost, "Améliorer la puissance de clic ({} niveaux) - Coût : {}"),
+    (Text::TabPlay, "Jeu"),
+    (Text::TabStats, "Statistiques"),
+    (Text::StatLifetimeEarned, "Gains totaux"),
+    (Text::StatUpgradesPurchased, "Améliorations achetées"),
+    (Text::StatTimeOnline, "Temps de jeu"),
+    (Text::StatTimeOffline, "Temps d'absence"),
+    (Text::StatHighestProduction, "Production maximale par seconde"),
+    (Text::StatSaves, "Sauvegardes"),
+    (Text::StatLoads, "Chargements"),
+    (Text::StatResets, "Réinitialisations"),
+    (Text::StatSessionLength, "Session en cours"),
+    (Text::AutosaveEvery, "Sauvegarde automatique toutes les"),
+    (Text::Seconds, "{} s"),
+    (Text::SaveStatusSaved, "Progression sauvegardée"),
+    (Text::SaveStatusPending, "Modifications non sauvegardées"),
+    (Text::SaveStatusError, "Échec de la sauvegarde : {}"),
+    (Text::TabBackups, "Sauvegardes de secours"),
+    (Text::BackupRecent, "Sauvegarde automatique"),
+    (Text::BackupDaily, "Instantané quotidien"),
+    (Text::BackupSummary, "Compteur : {} - Production : {}/s"),
+    (Text::RestoreBackup, "Restaurer"),
+    (Text::NoBackups, "Aucune sauvegarde de secours. Une est créée à chaque sauvegarde."),
+    (Text::BackupError, "Sauvegardé, mais les sauvegardes de secours n'ont pas été mises à jour : {}"),
+    (Text::ConfirmTitle, "Êtes-vous sûr ?"),
+    (Text::ConfirmReset, "Réinitialiser cette partie ? Toute la progression de cet emplacement sera perdue."),
+    (Text::ConfirmLoad, "Charger la dernière sauvegarde ? La progression depuis sera perdue."),
+    (Text::ConfirmImport, "Importer cette sauvegarde ? Elle remplacera la partie en cours."),
+    (Text::ConfirmRestore, "Restaurer cette sauvegarde de secours ? Elle remplacera la partie en cours."),
+    (Text::Cancel, "Annuler"),
+    (Text::UndoneReset, "Partie réinitialisée."),
+    (Text::UndoneLoad, "Sauvegarde chargée."),
+    (Text::UndoneImport, "Sauvegarde importée."),
+    (Text::UndoneRestore, "Sauvegarde de secours restaurée."),
+    (Text::UndoneSync, "Remplacée par la partie du serveur."),
+    (Text::Undo, "Annuler ({} s)"),
+    (Text::ExportReplay, "Exporter un replay pour un rapport de bug"),
+    (Text::CloudSync, "Synchronisation en ligne"),
+    (Text::SyncEndpoint, "URL du serveur de synchronisation"),
+    (Text::SyncToken, "Jeton de synchronisation"),
+    (Text::SyncNow, "Synchroniser"),
+    (Text::SyncOff, "Indiquez un serveur pour synchroniser cet emplacement."),
+    (Text::SyncBusy, "Synchronisation..."),
+    (Text::SyncUpToDate, "Synchronisé avec le serveur."),
+    (Text::SyncPushed, "Partie envoyée au serveur."),
+    (Text::SyncPulled, "Partie du serveur récupérée."),
+    (Text::SyncFailed, "Échec de la synchronisation : {}"),
+    (Text::SyncConflictTitle, "Deux parties différentes"),
+    (Text::SyncConflict, "Ce navigateur et le serveur ont chacun une progression que l'autre n'a pas vue. Quelle partie voulez-vous garder ?"),
+    (Text::SyncThisBrowser, "Ce navigateur"),
+    (Text::SyncServer, "Serveur"),
+    (Text::SyncLifetime, "{} gagnés au total"),
+    (Text::SyncFurtherAhead, "Plus avancée"),
+    (Text::SyncKeepLocal, "Garder ce navigateur"),
+    (Text::SyncKeepRemote, "Garder le serveur"),
+    (Text::MinutesSeconds, "{} min {} s"),
+    (Text::HoursMinutes, "{} h {} min"),
+    (Text::GeneratorCursor, "Curseur"),
+    (Text::GeneratorFarm, "Ferme"),
+    (Text::GeneratorFactory, "Usine"),
+    (Text::GeneratorMine, "Mine"),
+    (Text::GeneratorBank, "Banque"),
+    (Text::AchievementPocketChange, "Menue monnaie"),
+    (Text::AchievementPocketChangeGoal, "Avoir 1 k d'un coup"),
+    (Text::AchievementMillionaire, "Millionnaire"),
+    (Text::AchievementMillionaireGoal, "Avoir 1 M d'un coup"),
+    (Text::AchievementIndustrialScale, "Échelle industrielle"),
+    (Text::AchievementIndustrialScaleGoal, "Produire 1 Md par seconde"),
+    (Text::AchievementTinkerer, "Bricoleur"),
+    (Text::AchievementTinkererGoal, "Acheter 10 améliorations de production"),
+    (Text::AchievementCollector, "Collectionneur"),
+    (Text::AchievementCollectorGoal, "Posséder 100 générateurs"),
+    (Text::AchievementGoodNightsSleep, "Bonne nuit de sommeil"),
+    (Text::AchievementGoodNightsSleepGoal, "Revenir après 8 heures d'absence"),
+    (Text::AchievementBornAgain, "Renaissance"),
+    (Text::AchievementBornAgainGoal, "Gagner un point de prestige"),
+    (Text::UpgradeSharperCursors, "Curseurs affûtés"),
+    (Text::UpgradeSharperCursorsEffect, "Les curseurs produisent deux fois plus"),
+    (Text::UpgradeFertileFields, "Champs fertiles"),
+    (Text::UpgradeFertileFieldsEffect, "Les fermes produisent deux fois plus"),
+    (Text::UpgradeAssemblyLines, "Chaînes de montage"),
+    (Text::UpgradeAssemblyLinesEffect, "Les usines produisent deux fois plus"),
+    (Text::UpgradeDeepShafts, "Puits profonds"),
+    (Text::UpgradeDeepShaftsEffect, "Les mines produisent deux fois plus"),
+    (Text::UpgradeCompoundInterest, "Intérêts composés"),
+    (Text::UpgradeCompoundInterestEffect, "Les banques produisent deux fois plus"),
+    (Text::UpgradeSynergy, "Synergie"),
+    (Text::UpgradeSynergyEffect, "Toute la production +50 %"),
+    (Text::UpgradeMassProduction, "Production de masse"),
+    (Text::UpgradeMassProductionEffect, "Toute la production x2"),
+    (Text::UpgradeStrongFingers, "Doigts musclés"),
+    (Text::UpgradeStrongFingersEffect, "Les clics valent trois fois plus"),
+    (Text::UpgradeIronGrip, "Poigne de fer"),
+    (Text::UpgradeIronGripEffect, "Les clics valent cinq fois plus"),
+    (Text::UpgradeNightShift, "Équipe de nuit"),
+    (Text::UpgradeNightShiftEffect, "+15 % d'efficacité hors ligne"),
+    (Text::UpgradeAutomation, "Automatisation"),
+    (Text::UpgradeAutomationEffect, "+25 % d'efficacité hors ligne"),
+    (Text::ErrorNotASaveCode, "Ce n'est pas un code de sauvegarde"),
+    (Text::ErrorSaveCodeTruncated, "Le code de sauvegarde est tronqué"),
+    (Text::ErrorSaveCodeCorrupted, "Le code de sauvegarde est corrompu"),
+    (Text::ErrorSaveCodeChecksum, "La somme de contrôle du code de sauvegarde ne correspond pas"),
+    (Text::ErrorSaveCodeInvalid, "Le code de sauvegarde n'est pas valide : {}"),
+    (Text::ErrorBackupNotFound, "Sauvegarde de secours introuvable"),
+    (Text::ErrorNothingSaved, "Rien n'est sauvegardé dans cet emplacement"),
+    (Text::ErrorNotSaved, "La partie n'a pas pu être sauvegardée"),
+    (Text::ErrorSyncToken, "Le serveur de synchronisation a refusé le jeton"),
+    (Text::ErrorSyncStatus, "Le serveur de synchronisation a répondu {}"),
+    (Text::ErrorSyncInvalidSave, "Le serveur de synchronisation a envoyé une sauvegarde non valide : {}"),
+];
+
+const SPANISH: &[(Text, &str)] = &[
+    (Text::Title, "Juego incremental de números grandes"),
+    (Text::Counter, "Contador"),
+    (Text::ProductionPerSecond, "Producción por segundo"),
+    (Text::PrestigePoints, "Puntos de prestigio"),
+    (Text::PrestigeMultiplier, "Producción x{}%"),
+    (Text::PrestigeFor, "Prestigio por +{} puntos"),
+    (Text::GeneratorOutput, "+{} por segundo cada uno"),
+    (Text::BuyCost, "Comprar - Coste: {}"),
+    (Text::UpgradeCost, "Mejorar producción (doble) - Coste: {}"),
+    (Text::UpgradeMaxed, "Mejorar producción (máximo)"),
+    (Text::SaveGame, "Guardar partida"),
+    (Text::LoadGame, "Cargar partida"),
+    (Text::ResetGame, "Reiniciar partida"),
+    (Text::SwitchSlot, "Cambiar ranura (jugando {})"),
+    (Text::Achievements, "Logros ({}/{})"),
+    (Text::AchievementBonus, "+{}% de producción"),
+    (Text::AchievementUnlocked, "Logro desbloqueado: {}"),
+    (Text::ExportSave, "Exportar partida"),
+    (Text::ImportPlaceholder, "Pega un código de partida para importarla"),
+    (Text::ImportSave, "Importar partida"),
+    (Text::WelcomeBack, "¡Bienvenido de nuevo!"),
+    (Text::Continue, "Continuar"),
+    (Text::OfflineSummary, "Mientras estuviste fuera {} ganaste {}."),
+    (Text::OfflineCapped, " Las ganancias sin conexión tienen un límite de {}."),
+    (Text::NumberNotation, "Notación numérica"),
+    (Text::Language, "Idioma"),
+    (Text::Storage, "Partidas en"),
+    (Text::LastSaved, "Guardado por última vez: {} ({})"),
+    (Text::Never, "Nunca"),
+    (Text::JustNow, "Justo ahora"),
+    (Text::MinutesAgo, "hace {} minutos"),
+    (Text::HoursAgo, "hace {} horas"),
+    (Text::SlotCounter, "Contador: {}"),
+    (Text::SlotDates, "Creada {} - guardada por última vez {}"),
+    (Text::NewGame, "Nueva partida"),
+    (Text::Play, "Jugar"),
+    (Text::SlotsError, "No se pudieron leer las ranuras: {}"),
+    (Text::CreateSlotError, "No se pudo crear la ranura: {}"),
+    (Text::NotationShort, "Sufijos cortos"),
+    (Text::NotationScientific, "Científica"),
+    (Text::NotationEngineering, "Ingeniería"),
+    (Text::NotationLongNames, "Nombres largos"),
+    (Text::NotationLogarithmic, "Logarítmica"),
+    (Text::Rate, "+{}/s"),
+    (Text::ClickTarget, "¡Clic! +{}"),
+    (Text::ClickUpgradeCost, "Mejorar poder de clic (doble) - Coste: {}"),
+    (Text::ClickStats, "{} clics han generado {}"),
+    (Text::UpgradeTree, "Árbol de mejoras"),
+    (Text::Purchased, "Comprado"),
+    (Text::Requires, "Requiere {}"),
+    (Text::BuyMax, "Máx."),
+    (Text::BuyManyCost, "Comprar {} - Coste: {}"),
+    (Text::UpgradeManyCost, "Mejorar producción ({} niveles) - Coste: {}"),
+    (Text::ClickUpgradeManyCost, "Mejorar poder de clic ({} niveles) - Coste: {}"),
+    (Text::TabPlay, "Juego"),
+    (Text::TabStats, "Estadísticas"),
+    (Text::StatLifetimeEarned, "Ganancias totales"),
+    (Text::StatUpgradesPurchased, "Mejoras compradas"),
+    (Text::StatTimeOnline, "Tiempo jugado"),
+    (Text::StatTimeOffline, "Tiempo ausente"),
+    (Text::StatHighestProduction, "Producción máxima por segundo"),
+    (Text::StatSaves, "Guardados"),
+    (Text::StatLoads, "Cargas"),
+    (Text::StatResets, "Reinicios"),
+    (Text::StatSessionLength, "Sesión actual"),
+    (Text::AutosaveEvery, "Autoguardado cada"),
+    (Text::Seconds, "{} s"),
+    (Text::SaveStatusSaved, "Progreso guardado"),
+    (Text::SaveStatusPending, "Cambios sin guardar"),
+    (Text::SaveStatusError, "Error al guardar: {}"),
+    (Text::TabBackups, "Copias de seguridad"),
+    (Text::BackupRecent, "Autoguardado"),
+    (Text::BackupDaily, "Copia diaria"),
+    (Text::BackupSummary, "Contador: {} - Producción: {}/s"),
+    (Text::RestoreBackup, "Restaurar"),
+    (Text::NoBackups, "Aún no hay copias. Se crean al guardar la partida."),
+    (Text::BackupError, "Guardada, pero no se actualizaron las copias: {}"),
+    (Text::ConfirmTitle, "¿Estás seguro?"),
+    (Text::ConfirmReset, "¿Reiniciar esta partida? Se perderá todo el progreso de esta ranura."),
+    (Text::ConfirmLoad, "¿Cargar la última partida guardada? Se perderá el progreso desde entonces."),
+    (Text::ConfirmImport, "¿Importar esta partida? Reemplazará la partida actual."),
+    (Text::ConfirmRestore, "¿Restaurar esta copia? Reemplazará la partida actual."),
+    (Text::Cancel, "Cancelar"),
+    (Text::UndoneReset, "Partida reiniciada."),
+    (Text::UndoneLoad, "Partida cargada."),
+    (Text::UndoneImport, "Partida importada."),
+    (Text::UndoneRestore, "Copia restaurada."),
+    (Text::UndoneSync, "Reemplazada por la partida del servidor."),
+    (Text::Undo, "Deshacer ({} s)"),
+    (Text::ExportReplay, "Exportar repetición para un informe de error"),
+    (Text::CloudSync, "Sincronización en la nube"),
+    (Text::SyncEndpoint, "URL del servidor de sincronización"),
+    (Text::SyncToken, "Token de sincronización"),
+    (Text::SyncNow, "Sincronizar ahora"),
+    (Text::SyncOff, "Indica un servidor para sincronizar esta ranura."),
+    (Text::SyncBusy, "Sincronizando..."),
+    (Text::SyncUpToDate, "Sincronizada con el servidor."),
+    (Text::SyncPushed, "Partida enviada al servidor."),
+    (Text::SyncPulled, "Se tomó la partida del servidor."),
+    (Text::SyncFailed, "Error al sincronizar: {}"),
+    (Text::SyncConflictTitle, "Dos partidas distintas"),
+    (Text::SyncConflict, "Este navegador y el servidor tienen progreso que el otro no ha visto. ¿Qué partida quieres conservar?"),
+    (Text::SyncThisBrowser, "Este navegador"),
+    (Text::SyncServer, "Servidor"),
+    (Text::SyncLifetime, "{} ganados en total"),
+    (Text::SyncFurtherAhead, "Más avanzada"),
+    (Text::SyncKeepLocal, "Conservar este navegador"),
+    (Text::SyncKeepRemote, "Conservar el servidor"),
+    (Text::MinutesSeconds, "{} min {} s"),
+    (Text::HoursMinutes, "{} h {} min"),
+    (Text::GeneratorCursor, "Cursor"),
+    (Text::GeneratorFarm, "Granja"),
+    (Text::GeneratorFactory, "Fábrica"),
+    (Text::GeneratorMine, "Mina"),
+    (Text::GeneratorBank, "Banco"),
+    (Text::AchievementPocketChange, "Calderilla"),
+    (Text::AchievementPocketChangeGoal, "Ten 1 mil a la vez"),
+    (Text::AchievementMillionaire, "Millonario"),
+    (Text::AchievementMillionaireGoal, "Ten 1 M a la vez"),
+    (Text::AchievementIndustrialScale, "Escala industrial"),
+    (Text::AchievementIndustrialScaleGoal, "Produce 1 mil M por segundo"),
+    (Text::AchievementTinkerer, "Manitas"),
+    (Text::AchievementTinkererGoal, "Compra 10 mejoras de producción"),
+    (Text::AchievementCollector, "Coleccionista"),
+    (Text::AchievementCollectorGoal, "Ten 100 generadores"),
+    (Text::AchievementGoodNightsSleep, "Una buena noche de sueño"),
+    (Text::AchievementGoodNightsSleepGoal, "Vuelve tras 8 horas fuera"),
+    (Text::AchievementBornAgain, "Renacido"),
+    (Text::AchievementBornAgainGoal, "Gana un punto de prestigio"),
+    (Text::UpgradeSharperCursors, "Cursores afilados"),
+    (Text::UpgradeSharperCursorsEffect, "Los cursores producen el doble"),
+    (Text::UpgradeFertileFields, "Campos fértiles"),
+    (Text::UpgradeFertileFieldsEffect, "Las granjas producen el doble"),
+    (Text::UpgradeAssemblyLines, "Cadenas de montaje"),
+    (Text::UpgradeAssemblyLinesEffect, "Las fábricas producen el doble"),
+    (Text::UpgradeDeepShafts, "Pozos profundos"),
+    (Text::UpgradeDeepShaftsEffect, "Las minas producen el doble"),
+    (Text::UpgradeCompoundInterest, "Interés compuesto"),
+    (Text::UpgradeCompoundInterestEffect, "Los bancos producen el doble"),
+    (Text::UpgradeSynergy, "Sinergia"),
+    (Text::UpgradeSynergyEffect, "Toda la producción +50%"),
+    (Text::UpgradeMassProduction, "Producción en masa"),
+    (Text::UpgradeMassProductionEffect, "Toda la producción x2"),
+    (Text::UpgradeStrongFingers, "Dedos fuertes"),
+    (Text::UpgradeStrongFingersEffect, "Los clics valen el triple"),
+    (Text::UpgradeIronGrip, "Puño de hierro"),
+    (Text::UpgradeIronGripEffect, "Los clics valen el quíntuple"),
+    (Text::UpgradeNightShift, "Turno de noche"),
+    (Text::UpgradeNightShiftEffect, "+15% de eficiencia sin conexión"),
+    (Text::UpgradeAutomation, "Automatización"),
+    (Text::UpgradeAutomationEffect, "+25% de eficiencia sin conexión"),
+    (Text::ErrorNotASaveCode, "No es un código de partida"),
+    (Text::ErrorSaveCodeTruncated, "El código de partida está incompleto"),
+    (Text::ErrorSaveCodeCorrupted, "El código de partida está dañado"),
+    (Text::ErrorSaveCodeChecksum, "La suma de verificación del código de partida no coincide"),
+    (Text::ErrorSaveCodeInvalid, "El código de partida no es válido: {}"),
+    (Text::ErrorBackupNotFound, "No se encontró la copia de seguridad"),
+    (Text::ErrorNothingSaved, "No hay nada guardado en esta ranura"),
+    (Text::ErrorNotSaved, "No se pudo guardar la partida"),
+    (Text::ErrorSyncToken, "El servidor de sincronización rechazó el token"),
+    (Text::ErrorSyncStatus, "El servidor de sincronización respondió {}"),
+    (Text::ErrorSyncInvalidSave, "El servidor de sincronización envió una partida no válida: {}"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env::MemoryStore;
+
+    #[test]
+    fn separators() {
+        assert_eq!(Locale::En.number("1234567.89"), "1,234,567.89");
+        assert_eq!(Locale::De.number("1234567.89"), "1.234.567,89");
+        assert_eq!(Locale::Es.number("12345"), "12.345");
+        assert_eq!(Locale::Fr.number("123456.5"), "123\u{202f}456,5");
+        // Short numbers stay ungrouped.
+        assert_eq!(Locale::En.number("1234"), "1234");
+        assert_eq!(Locale::De.number("1.5"), "1,5");
+        assert_eq!(Locale::De.number("999"), "999");
+    }
+
+    #[test]
+    fn fills_arguments_in_order() {
+        assert_eq!(Locale::En.fill(Text::Achievements, &["3", "7"]), "Achievements (3/7)");
+        assert_eq!(Locale::De.fill(Text::Achievements, &["3", "7"]), "Erfolge (3/7)");
+        assert_eq!(Locale::Fr.fill(Text::MinutesAgo, &["5"]), "il y a 5 minutes");
+        // Missing arguments leave the gap empty rather than panicking.
+        assert_eq!(Locale::En.fill(Text::Achievements, &["3"]), "Achievements (3/)");
+    }
+
+    #[test]
+    fn missing_translations_fall_back_to_english() {
+        let table = &[(Text::Counter, "Zähler")];
+        assert_eq!(lookup(table, Text::Counter), Some("Zähler"));
+        assert_eq!(lookup(table, Text::Play), None);
+        assert_eq!(Locale::En.text(Text::Play), "Play");
+    }
+
+    #[test]
+    fn translations_keep_their_placeholders() {
+        for locale in LOCALES {
+            for (i, (text, translated)) in locale.translations().iter().enumerate() {
+                assert_eq!(
+                    translated.matches("{}").count(),
+                    english(*text).matches("{}").count(),
+                    "{:?} {:?}",
+                    locale,
+                    text
+                );
+                assert!(
+                    locale.translations()[i + 1..].iter().all(|(other, _)| other != text),
+                    "{:?} translates {:?} twice",
+                    locale,
+                    text
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn tags() {
+        assert_eq!(Locale::from_tag("de-AT"), Some(Locale::De));
+        assert_eq!(Locale::from_tag("FR"), Some(Locale::Fr));
+        assert_eq!(Locale::from_tag("es_MX"), Some(Locale::Es));
+        assert_eq!(Locale::from_tag("ja-JP"), None);
+        for locale in LOCALES {
+            assert_eq!(Locale::from_tag(locale.tag()), Some(*locale));
+        }
+    }
+
+    #[test]
+    fn preference_round_trip() {
+        let store = MemoryStore::default();
+        assert_eq!(load_preference(&store).unwrap(), None);
+        save_preference(&store, Locale::Fr).unwrap();
+        assert_eq!(load_preference(&store).unwrap(), Some(Locale::Fr));
+        store.set(PREFERENCE_KEY, "xx").unwrap();
+        assert_eq!(load_preference(&store).unwrap(), None);
+    }
+
+    #[test]
+    fn plurals() {
+        assert!(Locale::De.is_singular("1"));
+        assert!(!Locale::De.is_singular("1.5"));
+        assert!(!Locale::Es.is_singular("2"));
+        assert!(Locale::Fr.is_singular("1.5"));
+        assert!(!Locale::Fr.is_singular("2"));
+    }
+}
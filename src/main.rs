@@ -1,7 +1,34 @@
 mod app;
+mod backups_tab;
+mod confirm_dialog;
+mod slot_picker;
+mod stats_tab;
+mod sync_panel;
+mod upgrade_tree;
+mod web;
 
-use app::App;
+use std::rc::Rc;
+
+use app::{App, AppProps};
+use idle_game::env::Env;
+use idle_game::journal::JournalClock;
+use web::{BrowserClock, StoreKind, WriteErrors};
 
 fn main() {
-    yew::Renderer::<App>::new().render();
+    wasm_bindgen_futures::spawn_local(async {
+        let write_errors = WriteErrors::default();
+        let (store_kind, store) = StoreKind::from_location().open(write_errors.clone()).await;
+        let clock = Rc::new(JournalClock::new(BrowserClock));
+        let env = Env {
+            clock: Box::new(clock.clone()),
+            store,
+        };
+        yew::Renderer::<App>::with_props(AppProps {
+            env: Rc::new(env),
+            clock,
+            store_kind,
+            write_errors,
+        })
+        .render();
+    });
 }
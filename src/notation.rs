@@ -0,0 +1,444 @@
+//! How big numbers are written out. Every notation shows values below 1000
+//! as plain numbers, and rounds everything half-up to two decimals.
+
+use serde::{Deserialize, Serialize};
+
+use crate::number::Number;
+use crate::locale::{Locale, Text};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Notation {
+    /// `1.5K`, `2.25Qa`, then `aa`, `ab`, ... once the named suffixes run out.
+    #[default]
+    Short,
+    /// `1.23e45`
+    Scientific,
+    /// `12.35e45`: scientific with the exponent a multiple of three.
+    Engineering,
+    /// `1.5 quadrillion`
+    LongNames,
+    /// `e45.09`: the base-10 logarithm.
+    Logarithmic,
+}
+
+pub const NOTATIONS: &[Notation] = &[
+    Notation::Short,
+    Notation::Scientific,
+    Notation::Engineering,
+    Notation::LongNames,
+    Notation::Logarithmic,
+];
+
+const DECIMALS: usize = 2;
+
+/// English short suffixes; other locales override a prefix of these.
+const SUFFIXES: &[&str] = &["", "K", "M", "B", "T", "Qa", "Qi", "Sx", "Sp", "Oc", "No", "Dc"];
+
+impl Notation {
+    pub fn label(self, locale: Locale) -> &'static str {
+        locale.text(match self {
+            Notation::Short => Text::NotationShort,
+            Notation::Scientific => Text::NotationScientific,
+            Notation::Engineering => Text::NotationEngineering,
+            Notation::LongNames => Text::NotationLongNames,
+            Notation::Logarithmic => Text::NotationLogarithmic,
+        })
+    }
+
+    pub fn format(self, num: &Number, locale: Locale) -> String {
+        let (digits, exponent) = num.digits();
+        self.format_digits(&digits, exponent, locale)
+    }
+
+    /// Format the number whose decimal digits are `digits`, with the first
+    /// one worth `10^exponent`.
+    pub fn format_digits(self, digits: &str, exponent: i64, locale: Locale) -> String {
+        if exponent < 3 {
+            // Anything that rounds to 1000 or more falls through.
+            let significant = exponent + 1 + DECIMALS as i64;
+            if significant < 0 || (significant == 0 && digits.as_bytes()[0] < b'5') {
+                return "0".to_string();
+            }
+            let (rounded, exponent) = match significant {
+                0 => ("1".to_string(), exponent + 1),
+                _ => round_significant(digits, exponent, significant as usize),
+            };
+            if exponent < 3 {
+                return locale.number(&fixed(&rounded, exponent));
+            }
+            return self.format_digits(&rounded, exponent, locale);
+        }
+        match self {
+            Notation::Short => {
+                let (mantissa, group) = grouped(digits, exponent);
+                let suffix = match locale.short_suffixes().get(group as usize) {
+                    Some(suffix) => suffix.to_string(),
+                    None => short_suffix(group as usize),
+                };
+                format!("{}{}", locale.number(&mantissa), suffix)
+            }
+            Notation::Scientific => {
+                let (rounded, exponent) = round_significant(digits, exponent, 1 + DECIMALS);
+                let mantissa = mantissa(&rounded, 1);
+                format!("{}e{}", locale.number(&mantissa), locale.number(&exponent.to_string()))
+            }
+            Notation::Engineering => {
+                let (mantissa, group) = grouped(digits, exponent);
+                let exponent = (group * 3).to_string();
+                format!("{}e{}", locale.number(&mantissa), locale.number(&exponent))
+            }
+            Notation::LongNames => {
+                let (mantissa, group) = grouped(digits, exponent);
+                let name = match locale.long_names() {
+                    None => long_name(group as usize),
+                    Some(names) => names.get(group as usize - 1).map(|(singular, plural)| {
+                        match locale.is_singular(&mantissa) {
+                            true => singular.to_string(),
+                            false => plural.to_string(),
+                        }
+                    }),
+                };
+                match name {
+                    Some(name) => format!("{} {}", locale.number(&mantissa), name),
+                    None => Notation::Scientific.format_digits(digits, exponent, locale),
+                }
+            }
+            Notation::Logarithmic => {
+                let log = format!("{:.*}", DECIMALS, log10(digits, exponent));
+                format!("e{}", locale.number(&log))
+            }
+        }
+    }
+}
+
+/// Round to `significant` digits, half up. Returns exactly that many digits
+/// and the exponent of the first, which moves up when rounding carries
+/// (`9.996` to three digits is `1.00e1`).
+fn round_significant(digits: &str, exponent: i64, significant: usize) -> (String, i64) {
+    let mut kept: Vec<u8> = digits.bytes().take(significant).collect();
+    kept.resize(significant, b'0');
+    let round_up = digits.as_bytes().get(significant).is_some_and(|&d| d >= b'5');
+    if !round_up {
+        return (String::from_utf8(kept).unwrap(), exponent);
+    }
+    for digit in kept.iter_mut().rev() {
+        if *digit == b'9' {
+            *digit = b'0';
+        } else {
+            *digit += 1;
+            return (String::from_utf8(kept).unwrap(), exponent);
+        }
+    }
+    // Every kept digit was a 9.
+    kept.insert(0, b'1');
+    kept.pop();
+    (String::from_utf8(kept).unwrap(), exponent + 1)
+}
+
+/// Mantissa and thousands-group index for notations that step by 10^3.
+fn grouped(digits: &str, exponent: i64) -> (String, i64) {
+    let whole = |exponent: i64| (exponent % 3) as usize + 1;
+    let (rounded, rounded_exponent) =
+        round_significant(digits, exponent, whole(exponent) + DECIMALS);
+    // A carry can only add a digit, so the shorter rounding is still exact.
+    (
+        mantissa(&rounded, whole(rounded_exponent)),
+        rounded_exponent / 3,
+    )
+}
+
+/// Plain `12.5` style number for digits whose first is worth `10^exponent`,
+/// without trailing zeros.
+fn fixed(digits: &str, exponent: i64) -> String {
+    if exponent >= 0 {
+        return mantissa(digits, exponent as usize + 1);
+    }
+    let zeros = "0".repeat((-exponent - 1) as usize);
+    mantissa(&format!("0{}{}", zeros, digits), 1)
+}
+
+/// `whole` integer digits, then the decimals with trailing zeros dropped.
+fn mantissa(digits: &str, whole: usize) -> String {
+    if digits.len() < whole {
+        return format!("{:0<width$}", digits, width = whole);
+    }
+    let (int, frac) = digits.split_at(whole);
+    let frac = frac.trim_end_matches('0');
+    if frac.is_empty() {
+        int.to_string()
+    } else {
+        format!("{}.{}", int, frac)
+    }
+}
+
+/// Suffix for `10^(3 * group)`: the named ones first, then every two-letter
+/// combination `aa`..`zz`, then three letters, and so on.
+fn short_suffix(group: usize) -> String {
+    if let Some(suffix) = SUFFIXES.get(group) {
+        return suffix.to_string();
+    }
+    let mut index = group - SUFFIXES.len();
+    let mut len = 2;
+    while index >= 26usize.pow(len) {
+        index -= 26usize.pow(len);
+        len += 1;
+    }
+    let mut letters = vec![b'a'; len as usize];
+    for letter in letters.iter_mut().rev() {
+        *letter += (index % 26) as u8;
+        index /= 26;
+    }
+    String::from_utf8(letters).unwrap()
+}
+
+const FIRST_ILLIONS: &[&str] = &[
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+    "sextillion",
+    "septillion",
+    "octillion",
+    "nonillion",
+];
+
+// Conway-Wechsler prefixes. The markers after each tens and hundreds prefix
+// say which letter some unit prefixes gain in front of it.
+const UNITS: &[&str] = &["", "un", "duo", "tre", "quattuor", "quin", "se", "septe", "octo", "nove"];
+const TENS: &[(&str, &str)] = &[
+    ("", ""),
+    ("deci", "n"),
+    ("viginti", "ms"),
+    ("triginta", "ns"),
+    ("quadraginta", "ns"),
+    ("quinquaginta", "ns"),
+    ("sexaginta", "n"),
+    ("septuaginta", "n"),
+    ("octoginta", "mx"),
+    ("nonaginta", ""),
+];
+const HUNDREDS: &[(&str, &str)] = &[
+    ("", ""),
+    ("centi", "nx"),
+    ("ducenti", "n"),
+    ("trecenti", "ns"),
+    ("quadringenti", "ns"),
+    ("quingenti", "ns"),
+    ("sescenti", "n"),
+    ("septingenti", "n"),
+    ("octingenti", "mx"),
+    ("nongenti", ""),
+];
+
+/// Name of `10^(3 * group)` on the short scale, built with the
+/// Conway-Wechsler system past the first ten. There are no names past the
+/// 999th illion.
+fn long_name(group: usize) -> Option<String> {
+    if let Some(name) = FIRST_ILLIONS.get(group - 1) {
+        return Some(name.to_string());
+    }
+    let n = group - 1;
+    if n >= 1000 {
+        return None;
+    }
+    let (unit, ten, hundred) = (n % 10, n / 10 % 10, n / 100);
+    let (tens, tens_marks) = TENS[ten];
+    let (hundreds, hundreds_marks) = HUNDREDS[hundred];
+    // The unit prefix takes its marker from whatever directly follows it.
+    let marks = if ten > 0 { tens_marks } else { hundreds_marks };
+    let unit = match (UNITS[unit], marks) {
+        ("tre", m) if m.contains('s') || m.contains('x') => "tres",
+        ("se", m) if m.contains('s') => "ses",
+        ("se", m) if m.contains('x') => "sex",
+        ("septe", m) if m.contains('m') => "septem",
+        ("septe", m) if m.contains('n') => "septen",
+        ("nove", m) if m.contains('m') => "novem",
+        ("nove", m) if m.contains('n') => "noven",
+        (unit, _) => unit,
+    };
+    let prefix = format!("{}{}{}", unit, tens, hundreds);
+    Some(format!("{}illion", prefix.trim_end_matches(['a', 'i'])))
+}
+
+fn log10(digits: &str, exponent: i64) -> f64 {
+    let leading = &digits[..digits.len().min(15)];
+    let mantissa: f64 = leading.parse().unwrap_or(1.0);
+    exponent as f64 + mantissa.log10() - (leading.len() as f64 - 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pow10(exponent: u32) -> Number {
+        Number::from(10u32).pow(exponent)
+    }
+
+    fn n(value: u64) -> Number {
+        Number::from(value)
+    }
+
+    #[test]
+    fn small_numbers_are_plain() {
+        for notation in NOTATIONS {
+            assert_eq!(notation.format(&n(0), Locale::En), "0");
+            assert_eq!(notation.format(&n(7), Locale::En), "7");
+            assert_eq!(notation.format(&n(999), Locale::En), "999");
+        }
+    }
+
+    #[test]
+    fn fractions_keep_two_decimals() {
+        let d = |s: &str| s.parse::<Number>().unwrap();
+        let cases = [
+            ("0.5", "0.5"),
+            ("1.15", "1.15"),
+            ("12.345", "12.35"),
+            ("0.004", "0"),
+            ("0.005", "0.01"),
+            ("0.0004", "0"),
+            ("9.999", "10"),
+            ("999.994", "999.99"),
+            ("999.995", "1K"),
+            ("1500.5", "1.5K"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(Notation::Short.format(&d(value), Locale::En), expected, "{}", value);
+        }
+        assert_eq!(Notation::Scientific.format(&d("999.999"), Locale::En), "1e3");
+        assert_eq!(Notation::Short.format(&d("0.25"), Locale::De), "0,25");
+    }
+
+    #[test]
+    fn short_suffixes() {
+        let cases = [
+            (n(1_000), "1K"),
+            (n(1_500), "1.5K"),
+            (n(1_234), "1.23K"),
+            (n(1_235), "1.24K"),
+            (n(12_345), "12.35K"),
+            (n(999_994), "999.99K"),
+            (n(999_995), "1M"),
+            (n(123_456_789), "123.46M"),
+            (n(4_000_000_000), "4B"),
+            (pow10(15) * n(2), "2Qa"),
+            (pow10(33), "1Dc"),
+            (pow10(36), "1aa"),
+            (pow10(39), "1ab"),
+            (pow10(36 + 3 * 25), "1az"),
+            (pow10(36 + 3 * 26), "1ba"),
+            (pow10(36 + 3 * 675), "1zz"),
+            (pow10(36 + 3 * 676), "1aaa"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(Notation::Short.format(&value, Locale::En), expected, "{}", value);
+        }
+    }
+
+    #[test]
+    fn scientific() {
+        let cases = [
+            (n(1_000), "1e3"),
+            (n(1_234), "1.23e3"),
+            (n(1_235), "1.24e3"),
+            (n(9_995), "1e4"),
+            (n(123_456_789), "1.23e8"),
+            (pow10(100) * n(5), "5e100"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(Notation::Scientific.format(&value, Locale::En), expected, "{}", value);
+        }
+    }
+
+    #[test]
+    fn engineering() {
+        let cases = [
+            (n(1_000), "1e3"),
+            (n(12_345), "12.35e3"),
+            (n(123_456_789), "123.46e6"),
+            (n(999_999), "1e6"),
+            (pow10(100), "10e99"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(Notation::Engineering.format(&value, Locale::En), expected, "{}", value);
+        }
+    }
+
+    #[test]
+    fn long_names() {
+        let cases = [
+            (n(1_500), "1.5 thousand"),
+            (n(2_000_000), "2 million"),
+            (pow10(15), "1 quadrillion"),
+            (pow10(30), "1 nonillion"),
+            (pow10(33), "1 decillion"),
+            (pow10(36), "1 undecillion"),
+            (pow10(42), "1 tredecillion"),
+            (pow10(45), "1 quattuordecillion"),
+            (pow10(51), "1 sedecillion"),
+            (pow10(54), "1 septendecillion"),
+            (pow10(60), "1 novendecillion"),
+            (pow10(63), "1 vigintillion"),
+            (pow10(72), "1 tresvigintillion"),
+            (pow10(99) * n(10), "10 duotrigintillion"),
+            (pow10(303), "1 centillion"),
+            (pow10(309), "1 duocentillion"),
+            (pow10(3000) * n(25), "25 novenonagintanongentillion"),
+            (pow10(3003) * n(15), "1.5e3004"),
+        ];
+        for (value, expected) in cases {
+            let exponent = value.to_string().len() - 1;
+            assert_eq!(Notation::LongNames.format(&value, Locale::En), expected, "1e{}", exponent);
+        }
+    }
+
+    #[test]
+    fn logarithmic() {
+        let cases = [
+            (n(1_000), "e3.00"),
+            (n(1_234), "e3.09"),
+            (n(999_999), "e6.00"),
+            (pow10(45) * n(123), "e47.09"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(Notation::Logarithmic.format(&value, Locale::En), expected, "{}", value);
+        }
+    }
+
+    #[test]
+    fn localized() {
+        let cases = [
+            (Notation::Short, Locale::De, n(1_500), "1,5 Tsd."),
+            (Notation::Short, Locale::Fr, n(2_250_000), "2,25 M"),
+            (Notation::Short, Locale::Es, n(3_000_000_000), "3 mil M"),
+            // Past the German suffixes the English ones take over.
+            (Notation::Short, Locale::De, pow10(24), "1Sp"),
+            (Notation::Scientific, Locale::De, n(1_234), "1,23e3"),
+            (Notation::Scientific, Locale::De, pow10(12_345), "1e12.345"),
+            (Notation::Engineering, Locale::Fr, n(12_345), "12,35e3"),
+            (Notation::Logarithmic, Locale::Es, n(1_234), "e3,09"),
+            (Notation::LongNames, Locale::De, n(1_000_000), "1 Million"),
+            (Notation::LongNames, Locale::De, n(1_500_000), "1,5 Millionen"),
+            (Notation::LongNames, Locale::De, pow10(9), "1 Milliarde"),
+            (Notation::LongNames, Locale::Fr, n(1_500_000), "1,5 million"),
+            (Notation::LongNames, Locale::Fr, n(2_000_000_000), "2 milliards"),
+            (Notation::LongNames, Locale::Es, pow10(12) * n(3), "3 billones"),
+            (Notation::LongNames, Locale::Es, pow10(12), "1 billón"),
+            // Long-scale names stop at the table, English ones would mislead.
+            (Notation::LongNames, Locale::De, pow10(36), "1e36"),
+        ];
+        for (notation, locale, value, expected) in cases {
+            assert_eq!(notation.format(&value, locale), expected, "{:?} {:?}", notation, locale);
+        }
+    }
+
+    #[test]
+    fn rounding_carries() {
+        assert_eq!(round_significant("9996", 3, 3), ("100".to_string(), 4));
+        assert_eq!(round_significant("9994", 3, 3), ("999".to_string(), 3));
+        assert_eq!(round_significant("12", 1, 4), ("1200".to_string(), 1));
+    }
+}
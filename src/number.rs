@@ -0,0 +1,62 @@
+//! The number type the game computes with. [`Decimal`] is exact to 18
+//! decimal places but gets slower as values grow; the `big-float` feature
+//! swaps in [`BigFloat`], which costs the same at any size but keeps only
+//! about 15 significant digits. Both offer the same operations and read each
+//! other's serialized form, so saves move freely between builds.
+
+pub use crate::big_float::BigFloat;
+pub use crate::decimal::Decimal;
+
+#[cfg(not(feature = "big-float"))]
+pub type Number = Decimal;
+#[cfg(feature = "big-float")]
+pub type Number = BigFloat;
+
+/// Implement arithmetic operators for every owned/borrowed combination of
+/// `$ty`, each through a single `fn(&$ty, &$ty) -> $ty`.
+macro_rules! binops {
+    ($ty:ty; $($trait:ident $method:ident $op:ident),* $(,)?) => {$(
+        impl std::ops::$trait<&$ty> for &$ty {
+            type Output = $ty;
+
+            fn $method(self, rhs: &$ty) -> $ty {
+                $op(self, rhs)
+            }
+        }
+
+        impl std::ops::$trait<$ty> for &$ty {
+            type Output = $ty;
+
+            fn $method(self, rhs: $ty) -> $ty {
+                $op(self, &rhs)
+            }
+        }
+
+        impl std::ops::$trait<&$ty> for $ty {
+            type Output = $ty;
+
+            fn $method(self, rhs: &$ty) -> $ty {
+                $op(&self, rhs)
+            }
+        }
+
+        impl std::ops::$trait<$ty> for $ty {
+            type Output = $ty;
+
+            fn $method(self, rhs: $ty) -> $ty {
+                $op(&self, &rhs)
+            }
+        }
+    )*};
+}
+
+pub(crate) use binops;
+
+/// Parse a number literal in tests, as `d("1.15")`.
+#[cfg(test)]
+pub(crate) fn d<T: std::str::FromStr>(s: &str) -> T
+where
+    T::Err: std::fmt::Debug,
+{
+    s.parse().unwrap()
+}
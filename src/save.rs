@@ -0,0 +1,234 @@
+//! Save-format versioning. Saves are stored as JSON tagged with a
+//! `save_version`; older blobs are upgraded one version at a time until they
+//! match the current `State` layout.
+
+use num_bigint::BigUint;
+use serde_json::{json, Value};
+
+pub const CURRENT_SAVE_VERSION: u32 = 10;
+
+type Migration = fn(&mut Value) -> Result<(), String>;
+
+/// `MIGRATIONS[n]` upgrades a version `n + 1` save to version `n + 2`.
+const MIGRATIONS: &[Migration] = &[v1_to_v2, v2_to_v3, v3_to_v4, v4_to_v5, v5_to_v6, v6_to_v7, v7_to_v8, v8_to_v9, v9_to_v10];
+
+/// Upgrade a save of any known version to `CURRENT_SAVE_VERSION`.
+pub fn migrate(mut save: Value) -> Result<Value, String> {
+    let mut version = save_version(&save)?;
+    if version > CURRENT_SAVE_VERSION {
+        return Err(format!(
+            "save version {} is newer than this game supports ({})",
+            version, CURRENT_SAVE_VERSION
+        ));
+    }
+    while version < CURRENT_SAVE_VERSION {
+        MIGRATIONS[version as usize - 1](&mut save)
+            .map_err(|e| format!("migrating save from v{}: {}", version, e))?;
+        version += 1;
+    }
+    save["save_version"] = version.into();
+    Ok(save)
+}
+
+fn save_version(save: &Value) -> Result<u32, String> {
+    let fields = save.as_object().ok_or("save is not a JSON object")?;
+    match fields.get("save_version") {
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .filter(|&version| version >= 1)
+            .ok_or_else(|| format!("invalid save_version {}", version)),
+        // Saves from before versioning: generator counts only exist from v2 on.
+        None if fields.contains_key("generators") => Ok(2),
+        None => Ok(1),
+    }
+}
+
+/// v2 replaced the stored `production` total with per-tier generator counts.
+fn v1_to_v2(save: &mut Value) -> Result<(), String> {
+    let fields = save.as_object_mut().ok_or("save is not a JSON object")?;
+    let production: BigUint = fields
+        .remove("production")
+        .as_ref()
+        .and_then(Value::as_str)
+        .ok_or("missing production")?
+        .parse()
+        .map_err(|e| format!("production: {}", e))?;
+    // Production only ever grew by doubling from 1, so a single cursor plus
+    // that many doublings reproduces it.
+    let doublings = production.bits().saturating_sub(1);
+    fields.insert("upgrade_level".to_string(), doublings.into());
+    fields.insert("generators".to_string(), json!([1, 0, 0, 0, 0]));
+    Ok(())
+}
+
+/// v3 added prestige. Earlier runs only know their current counter, so that
+/// is the best estimate of lifetime earnings.
+fn v2_to_v3(save: &mut Value) -> Result<(), String> {
+    let fields = save.as_object_mut().ok_or("save is not a JSON object")?;
+    let counter = fields.get("counter").cloned().ok_or("missing counter")?;
+    fields.insert("lifetime_earned".to_string(), counter);
+    fields.insert("prestige_points".to_string(), "0".into());
+    Ok(())
+}
+
+/// v4 added achievements; nothing was unlocked before them.
+fn v3_to_v4(save: &mut Value) -> Result<(), String> {
+    let fields = save.as_object_mut().ok_or("save is not a JSON object")?;
+    fields.insert("achievements".to_string(), json!([]));
+    Ok(())
+}
+
+/// v5 made the number notation a per-player setting; short suffixes were
+/// the only notation before.
+fn v4_to_v5(save: &mut Value) -> Result<(), String> {
+    let fields = save.as_object_mut().ok_or("save is not a JSON object")?;
+    fields.insert("notation".to_string(), "short".into());
+    Ok(())
+}
+
+/// v6 allows fractions in `counter`, `lifetime_earned` and
+/// `prestige_points`. Whole-number strings are valid decimals, so old values
+/// carry over unchanged; the bump keeps older builds from misreading a
+/// fractional save as corrupt instead of as too new.
+fn v5_to_v6(_save: &mut Value) -> Result<(), String> {
+    Ok(())
+}
+
+/// v7 added clicking, with its own upgrades and statistics.
+fn v6_to_v7(save: &mut Value) -> Result<(), String> {
+    let fields = save.as_object_mut().ok_or("save is not a JSON object")?;
+    fields.insert("click_level".to_string(), 0.into());
+    fields.insert(
+        "click_stats".to_string(),
+        json!({ "clicks": 0, "earned": "0", "rejected": 0 }),
+    );
+    Ok(())
+}
+
+/// v8 added the upgrade tree.
+fn v7_to_v8(save: &mut Value) -> Result<(), String> {
+    let fields = save.as_object_mut().ok_or("save is not a JSON object")?;
+    fields.insert("upgrades".to_string(), json!([]));
+    Ok(())
+}
+
+/// v9 added statistics. Upgrade levels already bought are the only totals
+/// an older save can account for; the session starts at its last save.
+fn v8_to_v9(save: &mut Value) -> Result<(), String> {
+    let fields = save.as_object_mut().ok_or("save is not a JSON object")?;
+    let level = |key: &str| fields.get(key).and_then(Value::as_u64).unwrap_or(0);
+    let upgrades_purchased = level("upgrade_level")
+        + level("click_level")
+        + fields.get("upgrades").and_then(Value::as_array).map_or(0, Vec::len) as u64;
+    let last_save = fields.get("last_save").cloned().unwrap_or_else(|| 0.0.into());
+    fields.insert(
+        "stats".to_string(),
+        json!({
+            "upgrades_purchased": upgrades_purchased,
+            "online_ms": 0,
+            "offline_ms": 0,
+            "highest_production": "0",
+            "saves": 0,
+            "loads": 0,
+            "resets": 0,
+            "session_started_at": last_save,
+        }),
+    );
+    Ok(())
+}
+
+/// v10 made the autosave interval a setting; it used to be fixed at five
+/// seconds.
+fn v9_to_v10(save: &mut Value) -> Result<(), String> {
+    let fields = save.as_object_mut().ok_or("save is not a JSON object")?;
+    fields.insert("autosave_seconds".to_string(), 5.into());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(json: &str) -> Value {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn v1_fixtures_migrate_to_current() {
+        let fresh = migrate(fixture(include_str!("../fixtures/saves/v1_fresh.json"))).unwrap();
+        assert_eq!(
+            fresh,
+            json!({
+                "save_version": 10,
+                "counter": "0",
+                "generators": [1, 0, 0, 0, 0],
+                "upgrade_level": 0,
+                "lifetime_earned": "0",
+                "prestige_points": "0",
+                "achievements": [],
+                "notation": "short",
+                "autosave_seconds": 5,
+                "click_level": 0,
+                "click_stats": { "clicks": 0, "earned": "0", "rejected": 0 },
+                "upgrades": [],
+                "stats": {
+                    "upgrades_purchased": 0,
+                    "online_ms": 0,
+                    "offline_ms": 0,
+                    "highest_production": "0",
+                    "saves": 0,
+                    "loads": 0,
+                    "resets": 0,
+                    "session_started_at": 1714000000000.0,
+                },
+                "last_save": 1714000000000.0,
+                "last_saved_at": null,
+            })
+        );
+
+        let late = migrate(fixture(include_str!("../fixtures/saves/v1_late_game.json"))).unwrap();
+        assert_eq!(late["counter"], "123456789012345678901234567890");
+        assert_eq!(late["upgrade_level"], 10);
+        assert_eq!(late["stats"]["upgrades_purchased"], 10);
+        assert_eq!(late["lifetime_earned"], "123456789012345678901234567890");
+
+        // Saves from the priced-upgrade era already recorded a level; the
+        // doubling count derived from production takes precedence.
+        let priced =
+            migrate(fixture(include_str!("../fixtures/saves/v1_priced_upgrades.json"))).unwrap();
+        assert_eq!(priced["upgrade_level"], 2);
+        assert_eq!(priced["save_version"], CURRENT_SAVE_VERSION);
+    }
+
+    #[test]
+    fn current_saves_are_untouched() {
+        let save = json!({ "save_version": CURRENT_SAVE_VERSION, "counter": "7" });
+        assert_eq!(migrate(save.clone()).unwrap(), save);
+    }
+
+    #[test]
+    fn whole_number_saves_stay_as_they_are() {
+        let save = json!({ "save_version": 5, "counter": "123456789012345678901234567890" });
+        let migrated = migrate(save).unwrap();
+        assert_eq!(migrated["save_version"], CURRENT_SAVE_VERSION);
+        assert_eq!(migrated["counter"], "123456789012345678901234567890");
+    }
+
+    #[test]
+    fn unversioned_generator_saves_are_v2() {
+        let save = json!({ "counter": "7", "generators": [2], "upgrade_level": 1 });
+        let migrated = migrate(save).unwrap();
+        assert_eq!(migrated["save_version"], CURRENT_SAVE_VERSION);
+        assert_eq!(migrated["generators"], json!([2]));
+        assert_eq!(migrated["lifetime_earned"], "7");
+    }
+
+    #[test]
+    fn rejects_unknown_versions() {
+        assert!(migrate(json!({ "save_version": CURRENT_SAVE_VERSION + 1 })).is_err());
+        assert!(migrate(json!({ "save_version": 0 })).is_err());
+        assert!(migrate(json!({ "counter": "1" })).is_err());
+        assert!(migrate(json!([1, 2])).is_err());
+    }
+}
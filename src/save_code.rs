@@ -0,0 +1,88 @@
+//! Portable save codes: the save JSON, deflate-compressed, prefixed with a
+//! CRC-32 of the JSON and base64-encoded so it survives copy and paste.
+//!
+//! The checksum catches truncated or mistyped codes; it is not meant to stop
+//! a determined player from editing their own save.
+
+use std::io::{Read, Write};
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+
+use crate::error::Error;
+
+/// Marks the code layout, so a future format can be told apart.
+const PREFIX: &str = "IG1.";
+
+pub fn encode(json: &str) -> String {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+    encoder
+        .write_all(json.as_bytes())
+        .expect("writing to a Vec cannot fail");
+    let compressed = encoder.finish().expect("writing to a Vec cannot fail");
+
+    let mut bytes = crc32fast::hash(json.as_bytes()).to_be_bytes().to_vec();
+    bytes.extend(compressed);
+    format!("{}{}", PREFIX, URL_SAFE_NO_PAD.encode(bytes))
+}
+
+pub fn decode(code: &str) -> Result<String, Error> {
+    let payload = code
+        .trim()
+        .strip_prefix(PREFIX)
+        .ok_or(Error::NotASaveCode)?;
+    let bytes = URL_SAFE_NO_PAD
+        .decode(payload)
+        .map_err(|_| Error::SaveCodeCorrupted)?;
+    if bytes.len() < 4 {
+        return Err(Error::SaveCodeTruncated);
+    }
+    let (checksum, compressed) = bytes.split_at(4);
+
+    let mut json = String::new();
+    DeflateDecoder::new(compressed)
+        .read_to_string(&mut json)
+        .map_err(|_| Error::SaveCodeCorrupted)?;
+    if crc32fast::hash(json.as_bytes()).to_be_bytes() != checksum {
+        return Err(Error::SaveCodeChecksum);
+    }
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"{"save_version":2,"counter":"123456789","generators":[3,1,0,0,0]}"#;
+
+    #[test]
+    fn round_trip() {
+        let code = encode(JSON);
+        assert!(code.starts_with(PREFIX));
+        assert_eq!(decode(&code).unwrap(), JSON);
+        assert_eq!(decode(&format!("  {}\n", code)).unwrap(), JSON);
+    }
+
+    #[test]
+    fn rejects_damaged_codes() {
+        let code = encode(JSON);
+        assert!(decode("").is_err());
+        assert!(decode(&code[PREFIX.len()..]).is_err());
+        assert!(decode(&code[..code.len() - 6]).is_err());
+        assert!(decode(&format!("{}!", code)).is_err());
+    }
+
+    #[test]
+    fn rejects_tampered_payload() {
+        // Re-encode a different JSON body under the original checksum.
+        let bytes = URL_SAFE_NO_PAD.decode(&encode(JSON)[PREFIX.len()..]).unwrap();
+        let forged = URL_SAFE_NO_PAD.decode(&encode(&JSON.replace("123", "999"))[PREFIX.len()..]).unwrap();
+        let mut spliced = bytes[..4].to_vec();
+        spliced.extend(&forged[4..]);
+        let code = format!("{}{}", PREFIX, URL_SAFE_NO_PAD.encode(spliced));
+        assert_eq!(decode(&code), Err(Error::SaveCodeChecksum));
+    }
+}
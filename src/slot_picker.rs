@@ -0,0 +1,158 @@
+use std::rc::Rc;
+
+use idle_game::env::Env;
+use idle_game::format::format_time_ago;
+use idle_game::locale::{Locale, Text};
+use idle_game::notation::Notation;
+use idle_game::slots::{self, SlotMeta};
+use wasm_bindgen::JsValue;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[derive(Properties)]
+pub struct SlotPickerProps {
+    pub env: Rc<Env>,
+    pub locale: Locale,
+    /// Called with the slot to play once it holds a game.
+    pub on_select: Callback<usize>,
+}
+
+impl PartialEq for SlotPickerProps {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.env, &other.env)
+            && self.locale == other.locale
+            && self.on_select == other.on_select
+    }
+}
+
+/// Lists every save slot and starts or continues a game in one of them.
+#[function_component(SlotPicker)]
+pub fn slot_picker(props: &SlotPickerProps) -> Html {
+    let error = use_state(|| None::<String>);
+    let slots = match slots::list(props.env.store.as_ref()) {
+        Ok(slots) => slots,
+        Err(e) => {
+            return html! {
+                <div class="p-4 max-w-2xl mx-auto text-red-600">
+                    { props.locale.fill(Text::SlotsError, &[&e]) }
+                </div>
+            }
+        }
+    };
+
+    html! {
+        <div class="p-4 max-w-2xl mx-auto">
+            <h1 class="text-3xl font-bold mb-4 text-center">{ props.locale.text(Text::Title) }</h1>
+            <div class="bg-gray-100 rounded-lg p-4 flex flex-col gap-2">
+                { for slots.into_iter().enumerate().map(|(slot, meta)| html! {
+                    <SlotCard
+                        env={props.env.clone()}
+                        locale={props.locale}
+                        {slot}
+                        {meta}
+                        on_select={props.on_select.clone()}
+                        on_error={
+                            let error = error.clone();
+                            Callback::from(move |e| error.set(Some(e)))
+                        } />
+                }) }
+                if let Some(error) = &*error {
+                    <div class="text-sm text-red-600">{ error }</div>
+                }
+            </div>
+        </div>
+    }
+}
+
+#[derive(Properties)]
+struct SlotCardProps {
+    env: Rc<Env>,
+    locale: Locale,
+    slot: usize,
+    meta: Option<SlotMeta>,
+    on_select: Callback<usize>,
+    on_error: Callback<String>,
+}
+
+impl PartialEq for SlotCardProps {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.env, &other.env)
+            && self.locale == other.locale
+            && self.slot == other.slot
+            && self.meta == other.meta
+            && self.on_select == other.on_select
+            && self.on_error == other.on_error
+    }
+}
+
+#[function_component(SlotCard)]
+fn slot_card(props: &SlotCardProps) -> Html {
+    let name = use_state(String::new);
+    let slot = props.slot;
+    let locale = props.locale;
+
+    let Some(meta) = &props.meta else {
+        let on_name_input = {
+            let name = name.clone();
+            Callback::from(move |e: InputEvent| {
+                let input: HtmlInputElement = e.target_unchecked_into();
+                name.set(input.value());
+            })
+        };
+        let on_new_game = {
+            let env = props.env.clone();
+            let on_select = props.on_select.clone();
+            let on_error = props.on_error.clone();
+            let name = name.clone();
+            Callback::from(move |_| match slots::create(&env, slot, &name) {
+                Ok(_) => on_select.emit(slot),
+                Err(e) => on_error.emit(locale.fill(Text::CreateSlotError, &[&e])),
+            })
+        };
+        return html! {
+            <div class="bg-white p-3 rounded shadow flex items-center gap-2">
+                <input
+                    class="flex-1 p-2 rounded border"
+                    placeholder={slots::default_name(slot)}
+                    value={(*name).clone()}
+                    oninput={on_name_input} />
+                <button
+                    class="px-4 py-2 bg-green-500 text-white rounded hover:bg-green-600 transition-colors"
+                    onclick={on_new_game}>
+                    { locale.text(Text::NewGame) }
+                </button>
+            </div>
+        };
+    };
+
+    let on_play = {
+        let on_select = props.on_select.clone();
+        Callback::from(move |_| on_select.emit(slot))
+    };
+    let created = js_sys::Date::new(&JsValue::from_f64(meta.created))
+        .to_locale_date_string(locale.tag(), &JsValue::UNDEFINED);
+    html! {
+        <div class="bg-white p-3 rounded shadow flex items-center justify-between">
+            <div>
+                <div class="font-bold">{ &meta.name }</div>
+                <div class="text-gray-600 text-sm">
+                    { locale.fill(Text::SlotCounter, &[&Notation::default().format(&meta.counter, locale)]) }
+                </div>
+                <div class="text-gray-600 text-sm">
+                    { locale.fill(
+                        Text::SlotDates,
+                        &[
+                            &String::from(created),
+                            &format_time_ago(meta.last_saved, props.env.clock.now(), locale),
+                        ],
+                    ) }
+                </div>
+            </div>
+            <button
+                class="px-4 py-2 bg-blue-500 text-white rounded hover:bg-blue-600 transition-colors"
+                onclick={on_play}>
+                { locale.text(Text::Play) }
+            </button>
+        </div>
+    }
+}
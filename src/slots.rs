@@ -0,0 +1,172 @@
+//! Named save slots. Each slot keeps its `State` under its own storage key,
+//! and a shared index records what the slot picker shows about each one.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::number::Number;
+use crate::env::{Env, SaveStore};
+use crate::game::State;
+
+pub const SLOT_COUNT: usize = 3;
+
+pub(crate) const INDEX_KEY: &str = "idle_game_slots";
+
+/// Storage key of a slot's save. The first slot uses the key saves had
+/// before slots existed, so those saves show up there.
+pub fn slot_key(slot: usize) -> String {
+    match slot {
+        0 => "idle_game_save".to_string(),
+        _ => format!("idle_game_save_{}", slot),
+    }
+}
+
+/// What the slot picker shows about a slot without loading its save.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SlotMeta {
+    pub name: String,
+    pub created: f64,
+    pub last_saved: Option<f64>,
+    /// Counter as of the last save.
+    pub counter: Number,
+}
+
+/// Metadata for every slot, `None` where the slot is empty.
+pub fn list(store: &dyn SaveStore) -> Result<Vec<Option<SlotMeta>>, String> {
+    let mut slots: Vec<Option<SlotMeta>> = match store.get(INDEX_KEY)? {
+        Some(json) => serde_json::from_str(&json).map_err(|e| e.to_string())?,
+        None => vec![legacy_meta(store)?],
+    };
+    slots.resize(SLOT_COUNT, None);
+    Ok(slots)
+}
+
+/// Describe a save written before slots existed, if there is one.
+fn legacy_meta(store: &dyn SaveStore) -> Result<Option<SlotMeta>, String> {
+    let Some(json) = store.get(&slot_key(0))? else {
+        return Ok(None);
+    };
+    let save: Value = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+    let last_saved = save["last_saved_at"].as_f64();
+    Ok(Some(SlotMeta {
+        name: default_name(0),
+        created: last_saved.or(save["last_save"].as_f64()).unwrap_or_default(),
+        last_saved,
+        counter: save["counter"]
+            .as_str()
+            .and_then(|counter| counter.parse().ok())
+            .unwrap_or_default(),
+    }))
+}
+
+pub fn default_name(slot: usize) -> String {
+    format!("Slot {}", slot + 1)
+}
+
+fn write(store: &dyn SaveStore, slots: &[Option<SlotMeta>]) -> Result<(), String> {
+    let json = serde_json::to_string(slots).map_err(|e| e.to_string())?;
+    store.set(INDEX_KEY, &json)
+}
+
+/// Start a fresh game in `slot`, replacing whatever it held.
+pub fn create(env: &Env, slot: usize, name: &str) -> Result<State, String> {
+    let now = env.clock.now();
+    let mut slots = list(env.store.as_ref())?;
+    let name = match name.trim() {
+        "" => default_name(slot),
+        name => name.to_string(),
+    };
+    slots[slot] = Some(SlotMeta {
+        name,
+        created: now,
+        last_saved: None,
+        counter: Number::default(),
+    });
+    write(env.store.as_ref(), &slots)?;
+    State {
+        slot,
+        ..State::new(now)
+    }
+    .save(env)
+}
+
+/// Update a slot's metadata after its save was written.
+pub fn record_save(store: &dyn SaveStore, saved: &State) -> Result<(), String> {
+    let mut slots = list(store)?;
+    let meta = slots[saved.slot].get_or_insert_with(|| SlotMeta {
+        name: default_name(saved.slot),
+        created: saved.last_save,
+        last_saved: None,
+        counter: Number::default(),
+    });
+    meta.last_saved = saved.last_saved_at;
+    meta.counter = saved.counter.clone();
+    write(store, &slots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env::{Clock, ManualClock};
+    use crate::game::{reducer, Msg};
+    use std::rc::Rc;
+
+    #[test]
+    fn slots_start_empty() {
+        let env = Env::in_memory(ManualClock::new(1000.0));
+        assert_eq!(list(env.store.as_ref()).unwrap(), vec![None; SLOT_COUNT]);
+    }
+
+    #[test]
+    fn saves_stay_in_their_slot() {
+        let clock = Rc::new(ManualClock::new(1000.0));
+        let env = Env::in_memory(clock.clone());
+        let first = create(&env, 0, "Main").unwrap();
+        let second = create(&env, 2, "  ").unwrap();
+
+        clock.advance(5000.0);
+        let first = State {
+            counter: Number::from(42u32),
+            ..first
+        };
+        reducer(&first, Msg::Save, &env);
+
+        let slots = list(env.store.as_ref()).unwrap();
+        let meta = slots[0].as_ref().unwrap();
+        assert_eq!(meta.name, "Main");
+        assert_eq!(meta.created, 1000.0);
+        assert_eq!(meta.last_saved, Some(clock.now()));
+        assert_eq!(meta.counter, Number::from(42u32));
+        assert_eq!(slots[1], None);
+        assert_eq!(slots[2].as_ref().unwrap().name, "Slot 3");
+
+        let loaded = State::load(&env, 2).unwrap().unwrap();
+        assert_eq!(loaded.slot, 2);
+        assert_eq!(loaded.last_saved_at, second.last_saved_at);
+        assert_eq!(State::load(&env, 1).unwrap(), None);
+
+        // Resetting only touches the active slot.
+        let reset = reducer(&first, Msg::Reset, &env);
+        assert_eq!(reset.slot, 0);
+        reducer(&reset, Msg::Save, &env);
+        let untouched = State::load(&env, 2).unwrap().unwrap();
+        assert_eq!(untouched.last_saved_at, second.last_saved_at);
+    }
+
+    #[test]
+    fn pre_slot_save_appears_in_first_slot() {
+        let env = Env::in_memory(ManualClock::new(1000.0));
+        env.store
+            .set(
+                &slot_key(0),
+                include_str!("../fixtures/saves/v1_late_game.json"),
+            )
+            .unwrap();
+        let slots = list(env.store.as_ref()).unwrap();
+        let meta = slots[0].as_ref().unwrap();
+        assert_eq!(meta.name, "Slot 1");
+        assert_eq!(meta.last_saved, Some(1714003595000.0));
+        assert_eq!(meta.counter, "123456789012345678901234567890".parse().unwrap());
+        assert!(State::load(&env, 0).unwrap().is_some());
+    }
+}
@@ -0,0 +1,45 @@
+//! Running totals for the statistics tab. They are kept in the save and
+//! survive prestiges and resets.
+
+use num_traits::Zero;
+use serde::{Deserialize, Serialize};
+
+use crate::number::Number;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    /// Production and click-power levels plus upgrade tree nodes bought.
+    pub upgrades_purchased: u64,
+    /// Time credited by ticks while the game was open.
+    pub online_ms: u64,
+    /// Time the game was closed, whether or not all of it earned anything.
+    pub offline_ms: u64,
+    /// Highest production per second ever reached.
+    pub highest_production: Number,
+    pub saves: u64,
+    pub loads: u64,
+    pub resets: u64,
+    /// When the current session began: a new game, a load or an import.
+    pub session_started_at: f64,
+}
+
+impl Stats {
+    pub fn new(now: f64) -> Self {
+        Self {
+            upgrades_purchased: 0,
+            online_ms: 0,
+            offline_ms: 0,
+            highest_production: Number::zero(),
+            saves: 0,
+            loads: 0,
+            resets: 0,
+            session_started_at: now,
+        }
+    }
+
+    pub fn record_production(&mut self, production: Number) {
+        if production > self.highest_production {
+            self.highest_production = production;
+        }
+    }
+}
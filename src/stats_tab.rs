@@ -0,0 +1,47 @@
+use idle_game::format::format_duration;
+use idle_game::game::State;
+use idle_game::locale::{Locale, Text};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct StatsTabProps {
+    pub state: State,
+    /// Current time, for the length of the session.
+    pub now: f64,
+    pub locale: Locale,
+}
+
+/// Lifetime totals and the current session, one per row.
+#[function_component(StatsTab)]
+pub fn stats_tab(props: &StatsTabProps) -> Html {
+    let state = &props.state;
+    let stats = &state.stats;
+    let locale = props.locale;
+    let format = |num| state.notation.format(num, locale);
+    let count = |count: u64| locale.number(&count.to_string());
+    let duration = |ms: f64| format_duration(ms / 1000.0, locale);
+
+    let rows = [
+        (Text::StatLifetimeEarned, format(&state.lifetime_earned)),
+        (Text::StatHighestProduction, format(&stats.highest_production)),
+        (Text::StatUpgradesPurchased, count(stats.upgrades_purchased)),
+        (Text::StatTimeOnline, duration(stats.online_ms as f64)),
+        (Text::StatTimeOffline, duration(stats.offline_ms as f64)),
+        (Text::StatSessionLength, duration((props.now - stats.session_started_at).max(0.0))),
+        (Text::StatSaves, count(stats.saves)),
+        (Text::StatLoads, count(stats.loads)),
+        (Text::StatResets, count(stats.resets)),
+    ];
+    html! {
+        <div class="bg-gray-100 rounded-lg p-4 mb-4">
+            <table class="w-full">
+                { for rows.into_iter().map(|(label, value)| html! {
+                    <tr class="border-b last:border-0">
+                        <td class="py-2 text-gray-600">{ locale.text(label) }</td>
+                        <td class="py-2 text-right font-bold">{ value }</td>
+                    </tr>
+                }) }
+            </table>
+        </div>
+    }
+}
@@ -0,0 +1,444 @@
+//! Keeping a slot's save on a save server, so it can be played from more
+//! than one browser. The server holds one save per slot:
+//!
+//! - `GET {endpoint}/saves/{slot}` answers the save as a [`RemoteSave`], or
+//!   404 if there is none.
+//! - `PUT {endpoint}/saves/{slot}` with a [`PushBody`] replaces it, unless
+//!   the copy there is no longer the `base` the client last saw; then it
+//!   answers 409 with the copy it has.
+//!
+//! Both need `Authorization: Bearer {token}`. This module builds the requests,
+//! reads the answers and runs the whole sync in [`run`]; the front end only
+//! supplies the [`Transport`]. A sync always saves
+//! first, and [`plan`] decides which way to go from the stored save: the
+//! server's copy moved on if its timestamp changed, the local one if its
+//! lifetime progress did. Lifetime progress also breaks the tie when both
+//! copies moved on.
+
+use std::future::Future;
+
+use num_traits::Zero;
+use serde::{Deserialize, Serialize};
+
+use crate::env::{Env, SaveStore};
+use crate::error::Error;
+use crate::game::State;
+use crate::number::Number;
+use crate::slots;
+
+const CONFIG_KEY: &str = "idle_game_sync";
+
+/// Storage key of the copy `slot` was last in sync with.
+pub fn synced_key(slot: usize) -> String {
+    format!("idle_game_synced_{}", slot)
+}
+
+/// Where to sync to. Syncing is off while there is no endpoint.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncConfig {
+    pub endpoint: String,
+    pub token: String,
+}
+
+impl SyncConfig {
+    pub fn load(store: &dyn SaveStore) -> Result<Self, String> {
+        match store.get(CONFIG_KEY)? {
+            Some(json) => serde_json::from_str(&json).map_err(|e| e.to_string()),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn save(&self, store: &dyn SaveStore) -> Result<(), String> {
+        let json = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        store.set(CONFIG_KEY, &json)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.endpoint.trim().is_empty()
+    }
+
+    pub fn pull(&self, slot: usize) -> HttpRequest {
+        HttpRequest {
+            method: Method::Get,
+            url: self.url(slot),
+            headers: self.headers(),
+            body: None,
+        }
+    }
+
+    /// Replace the server's copy with `save`, if the server still has `base`.
+    pub fn push(&self, slot: usize, save: &RemoteSave, base: Option<f64>) -> HttpRequest {
+        let body = PushBody {
+            save: save.clone(),
+            base,
+        };
+        HttpRequest {
+            method: Method::Put,
+            url: self.url(slot),
+            headers: self.headers(),
+            body: Some(serde_json::to_string(&body).expect("PushBody always serializes")),
+        }
+    }
+
+    fn url(&self, slot: usize) -> String {
+        format!("{}/saves/{}", self.endpoint.trim().trim_end_matches('/'), slot)
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        vec![
+            ("Authorization".to_string(), format!("Bearer {}", self.token)),
+            ("Content-Type".to_string(), "application/json".to_string()),
+        ]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Method {
+    Get,
+    Put,
+}
+
+impl Method {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Put => "PUT",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+impl HttpRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+/// A save as the server keeps it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RemoteSave {
+    pub saved_at: f64,
+    pub lifetime_earned: Number,
+    /// The save as it was written to the slot.
+    pub save: String,
+}
+
+impl RemoteSave {
+    /// Wrap a save as `State::save` wrote it.
+    pub fn from_save(json: &str) -> Result<Self, String> {
+        let value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let state = State::from_save(value)?;
+        Ok(Self {
+            saved_at: state.last_saved_at.unwrap_or(state.last_save),
+            lifetime_earned: state.lifetime_earned,
+            save: json.to_string(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PushBody {
+    pub save: RemoteSave,
+    /// `saved_at` of the copy the client expects to replace.
+    pub base: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PushOutcome {
+    Pushed,
+    /// Someone else pushed first; this is what the server has now.
+    Rejected(RemoteSave),
+}
+
+pub fn read_pull(response: &HttpResponse) -> Result<Option<RemoteSave>, Error> {
+    match response.status {
+        200 => serde_json::from_str(&response.body)
+            .map(Some)
+            .map_err(|e| Error::SyncInvalidSave(e.to_string())),
+        404 => Ok(None),
+        status => Err(status_error(status)),
+    }
+}
+
+pub fn read_push(response: &HttpResponse) -> Result<PushOutcome, Error> {
+    match response.status {
+        200 | 204 => Ok(PushOutcome::Pushed),
+        409 => serde_json::from_str(&response.body)
+            .map(PushOutcome::Rejected)
+            .map_err(|e| Error::SyncInvalidSave(e.to_string())),
+        status => Err(status_error(status)),
+    }
+}
+
+fn status_error(status: u16) -> Error {
+    match status {
+        401 | 403 => Error::SyncTokenRejected,
+        status => Error::SyncStatus(status),
+    }
+}
+
+/// The save in `slot`, provided it was written at `since` or later. Syncs
+/// save first and read the save back through this, so a save that failed
+/// stops the sync instead of syncing an older copy.
+pub fn stored_save(store: &dyn SaveStore, slot: usize, since: f64) -> Result<RemoteSave, Error> {
+    let json = store
+        .get(&slots::slot_key(slot))?
+        .ok_or(Error::NothingSaved)?;
+    let save = RemoteSave::from_save(&json)?;
+    if save.saved_at < since {
+        return Err(Error::NotSaved);
+    }
+    Ok(save)
+}
+
+/// The copy a slot was last in sync with.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SyncMark {
+    pub saved_at: f64,
+    pub lifetime_earned: Number,
+}
+
+pub fn synced(store: &dyn SaveStore, slot: usize) -> Result<Option<SyncMark>, String> {
+    Ok(store
+        .get(&synced_key(slot))?
+        .and_then(|json| serde_json::from_str(&json).ok()))
+}
+
+pub fn record_synced(store: &dyn SaveStore, slot: usize, save: &RemoteSave) -> Result<(), String> {
+    let mark = SyncMark {
+        saved_at: save.saved_at,
+        lifetime_earned: save.lifetime_earned.clone(),
+    };
+    let json = serde_json::to_string(&mark).map_err(|e| e.to_string())?;
+    store.set(&synced_key(slot), &json)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SyncPlan {
+    UpToDate,
+    /// Send the local game, replacing `base` on the server.
+    Push { base: Option<f64> },
+    /// Only the server's copy moved on; take it.
+    Pull(RemoteSave),
+    /// Both copies moved on; the player picks one.
+    Conflict(RemoteSave),
+}
+
+/// Which way to sync `local`, the game just saved in this browser, given
+/// what the server has and the copy both were last in sync with. Saving
+/// always moves the timestamp, so the local game only counts as moved on if
+/// it earned something since.
+pub fn plan(local: &RemoteSave, remote: Option<RemoteSave>, synced: Option<&SyncMark>) -> SyncPlan {
+    let Some(remote) = remote else {
+        return SyncPlan::Push { base: None };
+    };
+    let remote_moved = synced.map(|mark| mark.saved_at) != Some(remote.saved_at);
+    let local_moved = match synced {
+        Some(mark) => local.lifetime_earned != mark.lifetime_earned,
+        // A game never synced from here only counts if it got anywhere.
+        None => !local.lifetime_earned.is_zero(),
+    };
+    match (local_moved, remote_moved) {
+        (false, false) => SyncPlan::UpToDate,
+        (true, false) => SyncPlan::Push {
+            base: Some(remote.saved_at),
+        },
+        (false, true) => SyncPlan::Pull(remote),
+        (true, true) => SyncPlan::Conflict(remote),
+    }
+}
+
+/// The copy to suggest keeping in a conflict: the one with more lifetime
+/// progress, or the newer one if they are level.
+pub fn suggest_local(local: &RemoteSave, remote: &RemoteSave) -> bool {
+    if local.lifetime_earned != remote.lifetime_earned {
+        return local.lifetime_earned > remote.lifetime_earned;
+    }
+    local.saved_at >= remote.saved_at
+}
+
+/// Where sync requests go: `fetch` in the browser, an in-process server in
+/// tests. Only network failures are errors; the flow reads the status.
+pub trait Transport {
+    fn send(&self, request: HttpRequest) -> impl Future<Output = Result<HttpResponse, String>>;
+}
+
+impl<F, Fut> Transport for F
+where
+    F: Fn(HttpRequest) -> Fut,
+    Fut: Future<Output = Result<HttpResponse, String>>,
+{
+    fn send(&self, request: HttpRequest) -> impl Future<Output = Result<HttpResponse, String>> {
+        self(request)
+    }
+}
+
+/// The game in the slot being synced, as the sync flow drives it.
+pub trait SyncedGame {
+    fn env(&self) -> &Env;
+    fn slot(&self) -> usize;
+    /// Save the game now; the save must be in storage when this returns,
+    /// unless saving failed.
+    fn save_game(&mut self);
+    /// Replace the game with this save from the server.
+    fn load_game(&mut self, save: String);
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SyncOutcome {
+    UpToDate,
+    Pushed,
+    Pulled,
+    /// Both copies moved on, or someone pushed first; the player picks one.
+    Conflict { local: RemoteSave, remote: RemoteSave },
+}
+
+/// Sync `game` with the server: save, pull, [`plan`], then push or take the
+/// server's copy. A conflict is handed back for the player to settle with
+/// [`push`] or [`take`].
+pub async fn run(
+    game: &mut impl SyncedGame,
+    config: &SyncConfig,
+    transport: &impl Transport,
+) -> Result<SyncOutcome, Error> {
+    let local = save_now(game)?;
+    let remote = read_pull(&transport.send(config.pull(game.slot())).await?)?;
+    let synced = synced(game.env().store.as_ref(), game.slot())?;
+    match plan(&local, remote, synced.as_ref()) {
+        SyncPlan::UpToDate => Ok(SyncOutcome::UpToDate),
+        SyncPlan::Push { base } => push(game, config, transport, &local, base).await,
+        SyncPlan::Pull(remote) => take(game, &remote),
+        SyncPlan::Conflict(remote) => Ok(SyncOutcome::Conflict { local, remote }),
+    }
+}
+
+/// Save the game and read it back, failing if it didn't make it to storage.
+pub fn save_now(game: &mut impl SyncedGame) -> Result<RemoteSave, Error> {
+    let since = game.env().clock.now();
+    game.save_game();
+    stored_save(game.env().store.as_ref(), game.slot(), since)
+}
+
+/// Send `local` to replace `base` on the server.
+pub async fn push(
+    game: &impl SyncedGame,
+    config: &SyncConfig,
+    transport: &impl Transport,
+    local: &RemoteSave,
+    base: Option<f64>,
+) -> Result<SyncOutcome, Error> {
+    match read_push(&transport.send(config.push(game.slot(), local, base)).await?)? {
+        PushOutcome::Pushed => {
+            record_synced(game.env().store.as_ref(), game.slot(), local)?;
+            Ok(SyncOutcome::Pushed)
+        }
+        PushOutcome::Rejected(remote) => Ok(SyncOutcome::Conflict {
+            local: local.clone(),
+            remote,
+        }),
+    }
+}
+
+/// Replace the game with the server's copy.
+pub fn take(game: &mut impl SyncedGame, remote: &RemoteSave) -> Result<SyncOutcome, Error> {
+    game.load_game(remote.save.clone());
+    record_synced(game.env().store.as_ref(), game.slot(), remote)?;
+    Ok(SyncOutcome::Pulled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env::MemoryStore;
+
+    fn saved(saved_at: f64, lifetime: u32) -> State {
+        State {
+            lifetime_earned: Number::from(lifetime),
+            last_save: saved_at,
+            last_saved_at: Some(saved_at),
+            ..State::new(0.0)
+        }
+    }
+
+    fn remote(saved_at: f64, lifetime: u32) -> RemoteSave {
+        let json = serde_json::to_string(&saved(saved_at, lifetime)).unwrap();
+        RemoteSave::from_save(&json).unwrap()
+    }
+
+    #[test]
+    fn plans_follow_which_copy_moved_on() {
+        let mark = SyncMark {
+            saved_at: 10.0,
+            lifetime_earned: Number::from(5u32),
+        };
+        let synced = Some(&mark);
+        assert_eq!(plan(&remote(10.0, 5), None, synced), SyncPlan::Push { base: None });
+        assert_eq!(plan(&remote(10.0, 5), Some(remote(10.0, 5)), synced), SyncPlan::UpToDate);
+        assert_eq!(
+            plan(&remote(20.0, 9), Some(remote(10.0, 5)), synced),
+            SyncPlan::Push { base: Some(10.0) }
+        );
+        // Saving again without earning anything is not moving on.
+        assert_eq!(plan(&remote(20.0, 5), Some(remote(10.0, 5)), synced), SyncPlan::UpToDate);
+        assert_eq!(plan(&remote(40.0, 5), Some(remote(30.0, 9)), synced), SyncPlan::Pull(remote(30.0, 9)));
+        assert_eq!(
+            plan(&remote(40.0, 9), Some(remote(30.0, 7)), synced),
+            SyncPlan::Conflict(remote(30.0, 7))
+        );
+    }
+
+    #[test]
+    fn first_sync_only_conflicts_over_real_progress() {
+        assert_eq!(plan(&remote(5.0, 0), Some(remote(30.0, 9)), None), SyncPlan::Pull(remote(30.0, 9)));
+        assert_eq!(
+            plan(&remote(5.0, 1), Some(remote(30.0, 9)), None),
+            SyncPlan::Conflict(remote(30.0, 9))
+        );
+    }
+
+    #[test]
+    fn conflicts_suggest_the_copy_further_ahead() {
+        assert!(suggest_local(&remote(10.0, 9), &remote(30.0, 7)));
+        assert!(!suggest_local(&remote(40.0, 7), &remote(30.0, 9)));
+        assert!(!suggest_local(&remote(10.0, 7), &remote(30.0, 7)));
+    }
+
+    #[test]
+    fn only_a_fresh_save_is_synced() {
+        let store = MemoryStore::default();
+        assert!(stored_save(&store, 0, 0.0).is_err());
+        let json = serde_json::to_string(&saved(10.0, 5)).unwrap();
+        store.set(&slots::slot_key(0), &json).unwrap();
+        assert_eq!(stored_save(&store, 0, 10.0), Ok(remote(10.0, 5)));
+        assert_eq!(stored_save(&store, 0, 20.0), Err(Error::NotSaved));
+    }
+
+    #[test]
+    fn responses_are_read_by_status() {
+        let response = |status, body: &str| HttpResponse {
+            status,
+            body: body.to_string(),
+        };
+        assert_eq!(read_pull(&response(404, "")), Ok(None));
+        assert_eq!(read_pull(&response(401, "")), Err(Error::SyncTokenRejected));
+        assert_eq!(read_push(&response(204, "")), Ok(PushOutcome::Pushed));
+        assert_eq!(read_push(&response(500, "")), Err(Error::SyncStatus(500)));
+        assert!(read_pull(&response(200, "{")).is_err());
+    }
+}
@@ -0,0 +1,290 @@
+use std::rc::Rc;
+
+use idle_game::env::Env;
+use idle_game::error::Error;
+use idle_game::game::State;
+use idle_game::locale::{Locale, Text};
+use idle_game::notation::Notation;
+use idle_game::number::Number;
+use idle_game::sync::{self, RemoteSave, SyncConfig, SyncOutcome, SyncedGame};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::web::send;
+
+#[derive(Properties)]
+pub struct SyncPanelProps {
+    pub env: Rc<Env>,
+    pub state: State,
+    pub locale: Locale,
+    /// Save the game now; the save must be in storage when this returns,
+    /// unless saving failed.
+    pub on_save: Callback<()>,
+    /// Replace the game with this save from the server.
+    pub on_load: Callback<String>,
+}
+
+impl PartialEq for SyncPanelProps {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.env, &other.env)
+            && self.state == other.state
+            && self.locale == other.locale
+            && self.on_save == other.on_save
+            && self.on_load == other.on_load
+    }
+}
+
+#[derive(Clone, PartialEq)]
+enum Status {
+    Idle,
+    Busy,
+    Done(Text),
+    Failed(Error),
+}
+
+/// The two copies of a game that moved on in two places.
+#[derive(Clone, PartialEq)]
+struct Conflict {
+    local: RemoteSave,
+    remote: RemoteSave,
+}
+
+/// Everything a sync needs once it has left the component.
+#[derive(Clone)]
+struct Syncer {
+    config: SyncConfig,
+    env: Rc<Env>,
+    slot: usize,
+    on_save: Callback<()>,
+    on_load: Callback<String>,
+    status: UseStateHandle<Status>,
+    conflict: UseStateHandle<Option<Conflict>>,
+}
+
+impl SyncedGame for Syncer {
+    fn env(&self) -> &Env {
+        &self.env
+    }
+
+    fn slot(&self) -> usize {
+        self.slot
+    }
+
+    fn save_game(&mut self) {
+        self.on_save.emit(());
+    }
+
+    fn load_game(&mut self, save: String) {
+        self.on_load.emit(save);
+    }
+}
+
+impl Syncer {
+    async fn sync(mut self) {
+        let config = self.config.clone();
+        let result = sync::run(&mut self, &config, &send).await;
+        self.finish(result);
+    }
+
+    fn finish(&self, result: Result<SyncOutcome, Error>) {
+        self.status.set(match result {
+            Ok(SyncOutcome::UpToDate) => Status::Done(Text::SyncUpToDate),
+            Ok(SyncOutcome::Pushed) => Status::Done(Text::SyncPushed),
+            Ok(SyncOutcome::Pulled) => Status::Done(Text::SyncPulled),
+            Ok(SyncOutcome::Conflict { local, remote }) => {
+                self.conflict.set(Some(Conflict { local, remote }));
+                Status::Idle
+            }
+            Err(e) => Status::Failed(e),
+        });
+    }
+}
+
+/// Server settings and a button to sync the slot, plus the choice between
+/// the two games when both moved on.
+#[function_component(SyncPanel)]
+pub fn sync_panel(props: &SyncPanelProps) -> Html {
+    let locale = props.locale;
+    let config = {
+        let env = props.env.clone();
+        use_state(move || SyncConfig::load(env.store.as_ref()).unwrap_or_default())
+    };
+    let status = use_state(|| Status::Idle);
+    let conflict = use_state(|| None::<Conflict>);
+
+    let syncer = Syncer {
+        config: (*config).clone(),
+        env: props.env.clone(),
+        slot: props.state.slot,
+        on_save: props.on_save.clone(),
+        on_load: props.on_load.clone(),
+        status: status.clone(),
+        conflict: conflict.clone(),
+    };
+
+    let edit = |update: fn(&mut SyncConfig, String)| {
+        let config = config.clone();
+        let status = status.clone();
+        let env = props.env.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut edited = (*config).clone();
+            update(&mut edited, input.value());
+            if let Err(e) = edited.save(env.store.as_ref()) {
+                status.set(Status::Failed(e.into()));
+            }
+            config.set(edited);
+        })
+    };
+    let on_endpoint_change = edit(|config, value| config.endpoint = value);
+    let on_token_change = edit(|config, value| config.token = value);
+
+    let on_sync = {
+        let syncer = syncer.clone();
+        Callback::from(move |_| {
+            syncer.status.set(Status::Busy);
+            spawn_local(syncer.clone().sync());
+        })
+    };
+
+    let status_line = match &*status {
+        _ if !config.is_enabled() => locale.text(Text::SyncOff).to_string(),
+        Status::Idle => String::new(),
+        Status::Busy => locale.text(Text::SyncBusy).to_string(),
+        Status::Done(text) => locale.text(*text).to_string(),
+        Status::Failed(e) => locale.fill(Text::SyncFailed, &[&e.message(locale)]),
+    };
+
+    html! {
+        <div class="bg-gray-100 rounded-lg p-4 mt-4 flex flex-col gap-2">
+            <div class="font-bold">{ locale.text(Text::CloudSync) }</div>
+            <input
+                class="w-full p-2 rounded border text-sm"
+                type="url"
+                placeholder={locale.text(Text::SyncEndpoint)}
+                value={config.endpoint.clone()}
+                onchange={on_endpoint_change} />
+            <input
+                class="w-full p-2 rounded border text-sm"
+                type="password"
+                placeholder={locale.text(Text::SyncToken)}
+                value={config.token.clone()}
+                onchange={on_token_change} />
+            <button
+                class="px-4 py-2 bg-gray-500 text-white rounded hover:bg-gray-600 transition-colors disabled:opacity-50"
+                disabled={!config.is_enabled() || *status == Status::Busy}
+                onclick={on_sync}>
+                { locale.text(Text::SyncNow) }
+            </button>
+            <div class={classes!("text-sm", if matches!(*status, Status::Failed(_)) { "text-red-600" } else { "text-gray-600" })}>
+                { status_line }
+            </div>
+            if let Some(conflict) = (*conflict).clone() {
+                <SyncConflict {conflict} notation={props.state.notation} {locale} {syncer} />
+            }
+        </div>
+    }
+}
+
+#[derive(Properties)]
+struct SyncConflictProps {
+    conflict: Conflict,
+    notation: Notation,
+    locale: Locale,
+    syncer: Syncer,
+}
+
+impl PartialEq for SyncConflictProps {
+    fn eq(&self, other: &Self) -> bool {
+        self.conflict == other.conflict && self.notation == other.notation && self.locale == other.locale
+    }
+}
+
+/// Shows both games side by side and keeps the one the player picks.
+#[function_component(SyncConflict)]
+fn sync_conflict(props: &SyncConflictProps) -> Html {
+    let locale = props.locale;
+    let local = &props.conflict.local;
+    let remote = &props.conflict.remote;
+    let suggest_local = sync::suggest_local(local, remote);
+
+    let copy = |label: Text, saved_at: f64, lifetime_earned: &Number, suggested: bool| {
+        let saved_at = js_sys::Date::new(&JsValue::from_f64(saved_at))
+            .to_locale_string(locale.tag(), &JsValue::UNDEFINED);
+        html! {
+            <div class="flex-1 bg-gray-100 rounded p-3">
+                <div class="font-bold">{ locale.text(label) }</div>
+                <div class="text-sm text-gray-600">{ String::from(saved_at) }</div>
+                <div class="text-sm">
+                    { locale.fill(Text::SyncLifetime, &[&props.notation.format(lifetime_earned, locale)]) }
+                </div>
+                if suggested {
+                    <div class="text-sm text-green-600">{ locale.text(Text::SyncFurtherAhead) }</div>
+                }
+            </div>
+        }
+    };
+
+    let on_keep_local = {
+        let syncer = props.syncer.clone();
+        let base = Some(remote.saved_at);
+        Callback::from(move |_| {
+            syncer.conflict.set(None);
+            syncer.status.set(Status::Busy);
+            let mut syncer = syncer.clone();
+            spawn_local(async move {
+                // Push the game as it is now, which may have moved on while
+                // the player was choosing.
+                let result = match sync::save_now(&mut syncer) {
+                    Ok(local) => sync::push(&syncer, &syncer.config, &send, &local, base).await,
+                    Err(e) => Err(e),
+                };
+                syncer.finish(result);
+            });
+        })
+    };
+    let on_keep_remote = {
+        let syncer = props.syncer.clone();
+        let remote = remote.clone();
+        Callback::from(move |_| {
+            let mut syncer = syncer.clone();
+            syncer.conflict.set(None);
+            let result = sync::take(&mut syncer, &remote);
+            syncer.finish(result);
+        })
+    };
+    let on_cancel = {
+        let conflict = props.syncer.conflict.clone();
+        Callback::from(move |_| conflict.set(None))
+    };
+
+    html! {
+        <div class="fixed inset-0 bg-black bg-opacity-50 flex items-center justify-center">
+            <div class="bg-white rounded-lg shadow p-6 max-w-md text-center">
+                <h2 class="text-xl font-bold mb-2">{ locale.text(Text::SyncConflictTitle) }</h2>
+                <p class="mb-4">{ locale.text(Text::SyncConflict) }</p>
+                <div class="flex gap-2 mb-4">
+                    { copy(Text::SyncThisBrowser, local.saved_at, &local.lifetime_earned, suggest_local) }
+                    { copy(Text::SyncServer, remote.saved_at, &remote.lifetime_earned, !suggest_local) }
+                </div>
+                <div class="flex gap-2">
+                    <button
+                        class="flex-1 px-4 py-2 bg-blue-500 text-white rounded hover:bg-blue-600 transition-colors"
+                        onclick={on_keep_local}>
+                        { locale.text(Text::SyncKeepLocal) }
+                    </button>
+                    <button
+                        class="flex-1 px-4 py-2 bg-blue-500 text-white rounded hover:bg-blue-600 transition-colors"
+                        onclick={on_keep_remote}>
+                        { locale.text(Text::SyncKeepRemote) }
+                    </button>
+                </div>
+                <button class="mt-2 text-sm text-gray-500 hover:text-gray-700" onclick={on_cancel}>
+                    { locale.text(Text::Cancel) }
+                </button>
+            </div>
+        </div>
+    }
+}
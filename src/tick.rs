@@ -0,0 +1,131 @@
+//! Turns monotonic clock readings into the durations `Msg::Tick` carries, so
+//! production follows real time however irregularly the browser fires timers.
+
+use crate::game::Msg;
+
+/// The longest gap between ticks of a visible tab that still counts as
+/// play. Visible tabs fire their timers about once a second, so a longer gap
+/// means the machine was suspended, and it is sent as [`Msg::TimeAway`].
+/// Hidden tabs may have their timers throttled or frozen for any length of
+/// time, and that time is always played.
+pub const MAX_TICK_MS: u64 = 60_000;
+
+/// Measures the time between successive ticks.
+pub struct TickTimer {
+    last: f64,
+    /// Whether the tab has been hidden since the previous tick.
+    hidden: bool,
+}
+
+impl TickTimer {
+    pub fn new(now: f64) -> Self {
+        Self { last: now, hidden: false }
+    }
+
+    /// Whole milliseconds since the previous lap. The fraction left over is
+    /// carried into the next lap rather than dropped, and a clock reading
+    /// earlier than the last one counts as no time passing.
+    pub fn lap(&mut self, now: f64) -> u64 {
+        let elapsed = (now - self.last).max(0.0).floor();
+        self.last += elapsed;
+        elapsed as u64
+    }
+
+    /// Whole milliseconds the next lap would report, without taking it.
+    pub fn pending(&self, now: f64) -> u64 {
+        (now - self.last).max(0.0) as u64
+    }
+
+    /// Whole milliseconds the next tick would credit in full, for projecting
+    /// the counter between ticks without running ahead of what it credits.
+    pub fn pending_play(&self, now: f64) -> u64 {
+        let pending = self.pending(now);
+        if self.is_away(pending) {
+            0
+        } else {
+            pending
+        }
+    }
+
+    /// Take a lap and say how to credit it.
+    pub fn tick(&mut self, now: f64) -> Msg {
+        let elapsed_ms = self.lap(now);
+        if self.is_away(elapsed_ms) {
+            Msg::TimeAway(elapsed_ms)
+        } else {
+            Msg::Tick(elapsed_ms)
+        }
+    }
+
+    /// Settle up for the time before the tab was hidden or shown, then
+    /// count what follows as hidden or not.
+    pub fn visibility_changed(&mut self, now: f64, hidden: bool) -> Msg {
+        let msg = self.tick(now);
+        self.hidden = hidden;
+        msg
+    }
+
+    fn is_away(&self, elapsed_ms: u64) -> bool {
+        !self.hidden && elapsed_ms > MAX_TICK_MS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn laps_measure_irregular_gaps() {
+        let mut timer = TickTimer::new(100.0);
+        assert_eq!(timer.lap(1100.0), 1000);
+        assert_eq!(timer.lap(1350.0), 250);
+        // A throttled background tab.
+        assert_eq!(timer.lap(61_350.0), 60_000);
+        assert_eq!(timer.lap(61_350.0), 0);
+    }
+
+    #[test]
+    fn pending_does_not_lap() {
+        let mut timer = TickTimer::new(0.0);
+        assert_eq!(timer.pending(400.5), 400);
+        assert_eq!(timer.pending(900.0), 900);
+        assert_eq!(timer.lap(1000.0), 1000);
+        assert_eq!(timer.pending(1200.0), 200);
+    }
+
+    #[test]
+    fn fractions_carry_over() {
+        let mut timer = TickTimer::new(0.0);
+        let laps: u64 = (1..=10).map(|i| timer.lap(i as f64 * 16.7)).sum();
+        assert_eq!(laps, 167);
+    }
+
+    #[test]
+    fn clock_going_backwards_is_ignored() {
+        let mut timer = TickTimer::new(1000.0);
+        assert_eq!(timer.lap(900.0), 0);
+        assert_eq!(timer.lap(1500.0), 500);
+    }
+
+    #[test]
+    fn hidden_time_is_played_in_full() {
+        let mut timer = TickTimer::new(0.0);
+        assert_eq!(timer.visibility_changed(1000.0, true), Msg::Tick(1000));
+        // Timers frozen for ten minutes while hidden, then the tab shows.
+        assert_eq!(timer.pending_play(601_000.0), 600_000);
+        assert_eq!(timer.visibility_changed(601_000.0, false), Msg::Tick(600_000));
+        assert_eq!(timer.tick(602_000.0), Msg::Tick(1000));
+    }
+
+    #[test]
+    fn long_gaps_while_visible_are_time_away() {
+        let mut timer = TickTimer::new(0.0);
+        assert_eq!(timer.tick(MAX_TICK_MS as f64), Msg::Tick(MAX_TICK_MS));
+        // The machine slept with the tab open.
+        let woke = MAX_TICK_MS as f64 + 3_600_000.0;
+        assert_eq!(timer.pending_play(woke), 0);
+        assert_eq!(timer.tick(woke), Msg::TimeAway(3_600_000));
+        // Hiding settles the visible time before it the same way.
+        assert_eq!(timer.visibility_changed(woke + 3_600_000.0, true), Msg::TimeAway(3_600_000));
+    }
+}
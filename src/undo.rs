@@ -0,0 +1,37 @@
+//! Taking back a destructive action. Resetting, loading, importing,
+//! restoring a backup or taking the sync server's copy keeps the game as it
+//! was for a few seconds, and `Msg::Undo` puts it back.
+
+use crate::game::State;
+
+/// How long an action can be undone for.
+pub const UNDO_WINDOW_MS: f64 = 10_000.0;
+
+/// The destructive actions that can be undone.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UndoAction {
+    Reset,
+    Load,
+    Import,
+    RestoreBackup,
+    Sync,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Undo {
+    pub action: UndoAction,
+    /// The game before the action, without an undo of its own.
+    pub previous: State,
+    pub expires_at: f64,
+}
+
+impl Undo {
+    pub fn is_open(&self, now: f64) -> bool {
+        now < self.expires_at
+    }
+
+    /// Whole seconds left to undo, rounded up.
+    pub fn seconds_left(&self, now: f64) -> u64 {
+        ((self.expires_at - now).max(0.0) / 1000.0).ceil() as u64
+    }
+}
@@ -0,0 +1,89 @@
+use idle_game::game::State;
+use idle_game::locale::{Locale, Text};
+use idle_game::upgrades::{self, UpgradeNode};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct UpgradeTreeProps {
+    pub state: State,
+    pub locale: Locale,
+    /// Called with the id of the node to buy.
+    pub on_buy: Callback<String>,
+}
+
+/// The upgrade tree, each node indented under the first node it requires.
+#[function_component(UpgradeTree)]
+pub fn upgrade_tree(props: &UpgradeTreeProps) -> Html {
+    html! {
+        <div class="bg-gray-100 rounded-lg p-4 mb-4">
+            <h2 class="text-xl font-bold mb-2">{ props.locale.text(Text::UpgradeTree) }</h2>
+            { branch(props, None) }
+        </div>
+    }
+}
+
+fn branch(props: &UpgradeTreeProps, parent: Option<&str>) -> Html {
+    if upgrades::children(parent).next().is_none() {
+        return html! {};
+    }
+    let class = if parent.is_some() { "ml-6 pl-2 border-l-2 border-gray-300" } else { "" };
+    html! {
+        <ul {class}>
+            { for upgrades::children(parent).map(|node| html! {
+                <li class="mt-2">
+                    { node_card(props, node) }
+                    { branch(props, Some(node.id)) }
+                </li>
+            }) }
+        </ul>
+    }
+}
+
+fn node_card(props: &UpgradeTreeProps, node: &'static UpgradeNode) -> Html {
+    let state = &props.state;
+    let locale = props.locale;
+    let purchased = state.has_upgrade(node.id);
+    let unlocked = state.upgrade_unlocked(node.id);
+    let class = if purchased {
+        "bg-green-100 p-3 rounded shadow flex items-center justify-between"
+    } else if unlocked {
+        "bg-white p-3 rounded shadow flex items-center justify-between"
+    } else {
+        "bg-white p-3 rounded shadow flex items-center justify-between opacity-50"
+    };
+    let missing: Vec<&str> = node
+        .requires
+        .iter()
+        .filter(|required| !state.has_upgrade(required))
+        .filter_map(|required| upgrades::find(required))
+        .map(|required| locale.text(required.name))
+        .collect();
+    let label = if purchased {
+        locale.text(Text::Purchased).to_string()
+    } else {
+        locale.fill(Text::BuyCost, &[&state.notation.format(&node.price(), locale)])
+    };
+    let on_click = {
+        let on_buy = props.on_buy.clone();
+        Callback::from(move |_| on_buy.emit(node.id.to_string()))
+    };
+    html! {
+        <div {class}>
+            <div>
+                <div class="font-bold">{ locale.text(node.name) }</div>
+                <div class="text-gray-600 text-sm">{ locale.text(node.description) }</div>
+                if !missing.is_empty() {
+                    <div class="text-gray-600 text-sm">
+                        { locale.fill(Text::Requires, &[&missing.join(", ")]) }
+                    </div>
+                }
+            </div>
+            <button
+                class="px-4 py-2 bg-blue-500 text-white rounded hover:bg-blue-600 transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+                disabled={!state.can_buy_upgrade(node.id)}
+                onclick={on_click}>
+                { label }
+            </button>
+        </div>
+    }
+}
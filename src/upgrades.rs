@@ -0,0 +1,238 @@
+//! The upgrade tree. Each node costs counter, needs its prerequisite nodes
+//! bought first, and once bought applies its effect for the rest of the run.
+//! Purchased ids are kept in the save; a prestige clears them.
+
+use num_traits::One;
+
+use crate::generators::GENERATORS;
+use crate::locale::Text;
+use crate::number::Number;
+
+/// What a purchased node changes. Percentages multiply: `200` doubles.
+pub enum Effect {
+    /// Scales all production.
+    GlobalPercent(u32),
+    /// Scales the output of one generator tier.
+    TierPercent { tier: usize, percent: u32 },
+    /// Adds percentage points to the share of production earned offline.
+    OfflineEfficiency(u32),
+    /// Scales the click value.
+    ClickPercent(u32),
+}
+
+pub struct UpgradeNode {
+    /// Stable key stored in saves; never rename.
+    pub id: &'static str,
+    pub name: Text,
+    pub description: Text,
+    pub cost: u64,
+    /// Nodes that must be bought first. The tree view hangs each node under
+    /// the first of them.
+    pub requires: &'static [&'static str],
+    pub effect: Effect,
+}
+
+impl UpgradeNode {
+    pub fn price(&self) -> Number {
+        Number::from(self.cost)
+    }
+}
+
+/// Every node comes after its prerequisites, so the tree has no cycles.
+pub const UPGRADE_TREE: &[UpgradeNode] = &[
+    UpgradeNode {
+        id: "sharper_cursors",
+        name: Text::UpgradeSharperCursors,
+        description: Text::UpgradeSharperCursorsEffect,
+        cost: 100,
+        requires: &[],
+        effect: Effect::TierPercent { tier: 0, percent: 200 },
+    },
+    UpgradeNode {
+        id: "fertile_fields",
+        name: Text::UpgradeFertileFields,
+        description: Text::UpgradeFertileFieldsEffect,
+        cost: 1_000,
+        requires: &["sharper_cursors"],
+        effect: Effect::TierPercent { tier: 1, percent: 200 },
+    },
+    UpgradeNode {
+        id: "assembly_lines",
+        name: Text::UpgradeAssemblyLines,
+        description: Text::UpgradeAssemblyLinesEffect,
+        cost: 11_000,
+        requires: &["fertile_fields"],
+        effect: Effect::TierPercent { tier: 2, percent: 200 },
+    },
+    UpgradeNode {
+        id: "deep_shafts",
+        name: Text::UpgradeDeepShafts,
+        description: Text::UpgradeDeepShaftsEffect,
+        cost: 120_000,
+        requires: &["assembly_lines"],
+        effect: Effect::TierPercent { tier: 3, percent: 200 },
+    },
+    UpgradeNode {
+        id: "compound_interest",
+        name: Text::UpgradeCompoundInterest,
+        description: Text::UpgradeCompoundInterestEffect,
+        cost: 1_300_000,
+        requires: &["deep_shafts"],
+        effect: Effect::TierPercent { tier: 4, percent: 200 },
+    },
+    UpgradeNode {
+        id: "synergy",
+        name: Text::UpgradeSynergy,
+        description: Text::UpgradeSynergyEffect,
+        cost: 50_000,
+        requires: &["assembly_lines"],
+        effect: Effect::GlobalPercent(150),
+    },
+    UpgradeNode {
+        id: "mass_production",
+        name: Text::UpgradeMassProduction,
+        description: Text::UpgradeMassProductionEffect,
+        cost: 5_000_000,
+        requires: &["synergy", "compound_interest"],
+        effect: Effect::GlobalPercent(200),
+    },
+    UpgradeNode {
+        id: "strong_fingers",
+        name: Text::UpgradeStrongFingers,
+        description: Text::UpgradeStrongFingersEffect,
+        cost: 500,
+        requires: &[],
+        effect: Effect::ClickPercent(300),
+    },
+    UpgradeNode {
+        id: "iron_grip",
+        name: Text::UpgradeIronGrip,
+        description: Text::UpgradeIronGripEffect,
+        cost: 25_000,
+        requires: &["strong_fingers"],
+        effect: Effect::ClickPercent(500),
+    },
+    UpgradeNode {
+        id: "night_shift",
+        name: Text::UpgradeNightShift,
+        description: Text::UpgradeNightShiftEffect,
+        cost: 10_000,
+        requires: &[],
+        effect: Effect::OfflineEfficiency(15),
+    },
+    UpgradeNode {
+        id: "automation",
+        name: Text::UpgradeAutomation,
+        description: Text::UpgradeAutomationEffect,
+        cost: 1_000_000,
+        requires: &["night_shift"],
+        effect: Effect::OfflineEfficiency(25),
+    },
+];
+
+pub fn find(id: &str) -> Option<&'static UpgradeNode> {
+    UPGRADE_TREE.iter().find(|node| node.id == id)
+}
+
+/// Nodes shown directly under `parent` in the tree view, or the roots for
+/// `None`.
+pub fn children(parent: Option<&str>) -> impl Iterator<Item = &'static UpgradeNode> + '_ {
+    UPGRADE_TREE
+        .iter()
+        .filter(move |node| node.requires.first().copied() == parent)
+}
+
+/// The combined effect of a set of purchased nodes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Effects {
+    pub global: Number,
+    /// One multiplier per entry of `GENERATORS`.
+    pub tiers: Vec<Number>,
+    pub click: Number,
+    pub offline_efficiency_percent: u32,
+}
+
+pub fn effects(purchased: &[String]) -> Effects {
+    let mut effects = Effects {
+        global: Number::one(),
+        tiers: vec![Number::one(); GENERATORS.len()],
+        click: Number::one(),
+        offline_efficiency_percent: 0,
+    };
+    let percent = |percent: u32| Number::from_ratio(percent.into(), 100);
+    for node in purchased.iter().filter_map(|id| find(id)) {
+        match node.effect {
+            Effect::GlobalPercent(p) => effects.global = &effects.global * percent(p),
+            Effect::TierPercent { tier, percent: p } => {
+                effects.tiers[tier] = &effects.tiers[tier] * percent(p)
+            }
+            Effect::OfflineEfficiency(points) => effects.offline_efficiency_percent += points,
+            Effect::ClickPercent(p) => effects.click = &effects.click * percent(p),
+        }
+    }
+    effects
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn ids_are_unique() {
+        for (i, node) in UPGRADE_TREE.iter().enumerate() {
+            assert!(UPGRADE_TREE[i + 1..].iter().all(|other| other.id != node.id));
+        }
+    }
+
+    #[test]
+    fn prerequisites_come_first() {
+        for (i, node) in UPGRADE_TREE.iter().enumerate() {
+            for required in node.requires {
+                assert!(
+                    UPGRADE_TREE[..i].iter().any(|earlier| earlier.id == *required),
+                    "{} requires {}",
+                    node.id,
+                    required
+                );
+            }
+            if let Effect::TierPercent { tier, .. } = node.effect {
+                assert!(tier < GENERATORS.len());
+            }
+        }
+    }
+
+    #[test]
+    fn tree_view_shows_every_node_once() {
+        fn count(parent: Option<&str>) -> usize {
+            children(parent).map(|node| 1 + count(Some(node.id))).sum()
+        }
+        assert_eq!(count(None), UPGRADE_TREE.len());
+    }
+
+    #[test]
+    fn effects_compose() {
+        let none = effects(&[]);
+        assert_eq!(none.global, Number::one());
+        assert_eq!(none.offline_efficiency_percent, 0);
+
+        let some = effects(&ids(&[
+            "sharper_cursors",
+            "synergy",
+            "mass_production",
+            "strong_fingers",
+            "iron_grip",
+            "night_shift",
+            "automation",
+            "no_longer_exists",
+        ]));
+        assert_eq!(some.global, Number::from(3u32));
+        assert_eq!(some.tiers[0], Number::from(2u32));
+        assert_eq!(some.tiers[1], Number::one());
+        assert_eq!(some.click, Number::from(15u32));
+        assert_eq!(some.offline_efficiency_percent, 40);
+    }
+}
@@ -0,0 +1,314 @@
+//! Browser implementations of the game's environment.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use gloo::storage::{LocalStorage, SessionStorage, Storage};
+use idle_game::env::{Clock, MemoryStore, SaveStore};
+use idle_game::locale::Locale;
+use idle_game::sync::{HttpRequest, HttpResponse};
+use js_sys::{Array, Promise};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{console, Headers, IdbDatabase, IdbRequest, IdbTransactionMode, RequestInit, Response};
+use yew::Callback;
+
+pub struct BrowserClock;
+
+impl Clock for BrowserClock {
+    fn now(&self) -> f64 {
+        js_sys::Date::now()
+    }
+
+    fn monotonic(&self) -> f64 {
+        gloo::utils::window()
+            .performance()
+            .map_or_else(js_sys::Date::now, |performance| performance.now())
+    }
+}
+
+fn js_error(e: JsValue) -> String {
+    format!("{:?}", e)
+}
+
+pub struct LocalStore;
+
+impl SaveStore for LocalStore {
+    fn get(&self, key: &str) -> Result<Option<String>, String> {
+        LocalStorage::raw().get_item(key).map_err(js_error)
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), String> {
+        LocalStorage::raw().set_item(key, value).map_err(js_error)
+    }
+}
+
+/// Saves that only last as long as the browser tab.
+pub struct SessionStore;
+
+impl SaveStore for SessionStore {
+    fn get(&self, key: &str) -> Result<Option<String>, String> {
+        SessionStorage::raw().get_item(key).map_err(js_error)
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), String> {
+        SessionStorage::raw().set_item(key, value).map_err(js_error)
+    }
+}
+
+/// Where a store reports writes that fail after `set` has returned. The game
+/// listens while it's open, so the failure shows as a save error.
+#[derive(Clone, Default)]
+pub struct WriteErrors(Rc<RefCell<Option<Callback<String>>>>);
+
+impl WriteErrors {
+    pub fn listen(&self, callback: Callback<String>) {
+        *self.0.borrow_mut() = Some(callback);
+    }
+
+    pub fn stop_listening(&self) {
+        *self.0.borrow_mut() = None;
+    }
+
+    fn report(&self, e: String) {
+        console::log_1(&format!("Write error: {}", e).into());
+        if let Some(callback) = self.0.borrow().as_ref() {
+            callback.emit(e);
+        }
+    }
+}
+
+impl PartialEq for WriteErrors {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+const IDB_NAME: &str = "idle_game";
+const IDB_OBJECT_STORE: &str = "saves";
+
+/// Saves in IndexedDB, which isn't bound by LocalStorage's few-megabyte
+/// limit. IndexedDB is asynchronous, so every entry is read into memory when
+/// the store is opened and writes go through to the database in the
+/// background, reporting failures to `errors`.
+pub struct IndexedDbStore {
+    db: IdbDatabase,
+    cache: RefCell<HashMap<String, String>>,
+    errors: WriteErrors,
+}
+
+impl IndexedDbStore {
+    pub async fn open(errors: WriteErrors) -> Result<Self, String> {
+        let factory = gloo::utils::window()
+            .indexed_db()
+            .map_err(js_error)?
+            .ok_or("IndexedDB is not available")?;
+        let request = factory.open_with_u32(IDB_NAME, 1).map_err(js_error)?;
+        let upgrade_request = request.clone();
+        let on_upgrade = Closure::once_into_js(move || {
+            if let Ok(db) = upgrade_request.result() {
+                let db: IdbDatabase = db.unchecked_into();
+                if let Err(e) = db.create_object_store(IDB_OBJECT_STORE) {
+                    console::log_1(&e);
+                }
+            }
+        });
+        request.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+        let db: IdbDatabase = request_result(&request).await?.unchecked_into();
+
+        let store = db
+            .transaction_with_str(IDB_OBJECT_STORE)
+            .and_then(|transaction| transaction.object_store(IDB_OBJECT_STORE))
+            .map_err(js_error)?;
+        let keys = store.get_all_keys().map_err(js_error)?;
+        let values = store.get_all().map_err(js_error)?;
+        let keys: Array = request_result(&keys).await?.unchecked_into();
+        let values: Array = request_result(&values).await?.unchecked_into();
+        let cache = keys
+            .iter()
+            .zip(values.iter())
+            .filter_map(|(key, value)| Some((key.as_string()?, value.as_string()?)))
+            .collect();
+
+        Ok(Self {
+            db,
+            cache: RefCell::new(cache),
+            errors,
+        })
+    }
+}
+
+impl SaveStore for IndexedDbStore {
+    fn get(&self, key: &str) -> Result<Option<String>, String> {
+        Ok(self.cache.borrow().get(key).cloned())
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), String> {
+        let request = self
+            .db
+            .transaction_with_str_and_mode(IDB_OBJECT_STORE, IdbTransactionMode::Readwrite)
+            .and_then(|transaction| transaction.object_store(IDB_OBJECT_STORE))
+            .and_then(|store| store.put_with_key(&value.into(), &key.into()))
+            .map_err(js_error)?;
+        self.cache
+            .borrow_mut()
+            .insert(key.to_string(), value.to_string());
+        let errors = self.errors.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Err(e) = request_result(&request).await {
+                errors.report(e);
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Wait for an IndexedDB request to finish and return its result.
+async fn request_result(request: &IdbRequest) -> Result<JsValue, String> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        let success_request = request.clone();
+        let on_success = Closure::once_into_js(move || {
+            let result = success_request.result().unwrap_or(JsValue::UNDEFINED);
+            let _ = resolve.call1(&JsValue::NULL, &result);
+        });
+        let error_request = request.clone();
+        let on_error = Closure::once_into_js(move || {
+            let error = error_request
+                .error()
+                .ok()
+                .flatten()
+                .map_or(JsValue::UNDEFINED, JsValue::from);
+            let _ = reject.call1(&JsValue::NULL, &error);
+        });
+        request.set_onsuccess(Some(on_success.unchecked_ref()));
+        request.set_onerror(Some(on_error.unchecked_ref()));
+    });
+    JsFuture::from(promise).await.map_err(js_error)
+}
+
+/// Where saves are kept, chosen at startup with `?store=` in the page URL.
+/// The storage picker reloads the page with a different one.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum StoreKind {
+    #[default]
+    Local,
+    Session,
+    IndexedDb,
+    /// Nothing survives a reload; for trying things out.
+    Memory,
+}
+
+pub const STORE_KINDS: &[StoreKind] = &[
+    StoreKind::Local,
+    StoreKind::Session,
+    StoreKind::IndexedDb,
+    StoreKind::Memory,
+];
+
+impl StoreKind {
+    pub fn from_location() -> Self {
+        let search = gloo::utils::window()
+            .location()
+            .search()
+            .unwrap_or_default();
+        search
+            .trim_start_matches('?')
+            .split('&')
+            .find_map(|param| param.strip_prefix("store="))
+            .and_then(StoreKind::parse)
+            .unwrap_or_default()
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "local" => Some(StoreKind::Local),
+            "session" => Some(StoreKind::Session),
+            "indexeddb" => Some(StoreKind::IndexedDb),
+            "memory" => Some(StoreKind::Memory),
+            _ => None,
+        }
+    }
+
+    /// The `?store=` value that picks this store.
+    fn name(self) -> &'static str {
+        match self {
+            StoreKind::Local => "local",
+            StoreKind::Session => "session",
+            StoreKind::IndexedDb => "indexeddb",
+            StoreKind::Memory => "memory",
+        }
+    }
+
+    /// Reload the page with this store. Saves stay in the store they were
+    /// made in.
+    pub fn switch_to(self) {
+        let location = gloo::utils::window().location();
+        if let Err(e) = location.set_search(&format!("store={}", self.name())) {
+            console::log_1(&e);
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StoreKind::Local => "LocalStorage",
+            StoreKind::Session => "SessionStorage",
+            StoreKind::IndexedDb => "IndexedDB",
+            StoreKind::Memory => "memory",
+        }
+    }
+
+    /// Open the store, falling back to LocalStorage if IndexedDB can't be used.
+    pub async fn open(self, errors: WriteErrors) -> (Self, Box<dyn SaveStore>) {
+        match self {
+            StoreKind::Local => (self, Box::new(LocalStore)),
+            StoreKind::Session => (self, Box::new(SessionStore)),
+            StoreKind::Memory => (self, Box::new(MemoryStore::default())),
+            StoreKind::IndexedDb => match IndexedDbStore::open(errors).await {
+                Ok(store) => (self, Box::new(store)),
+                Err(e) => {
+                    console::log_1(&format!("IndexedDB error: {}", e).into());
+                    (StoreKind::Local, Box::new(LocalStore))
+                }
+            },
+        }
+    }
+}
+
+/// The first language the browser asks for, if the game speaks it.
+pub fn browser_locale() -> Option<Locale> {
+    gloo::utils::window()
+        .navigator()
+        .language()
+        .as_deref()
+        .and_then(Locale::from_tag)
+}
+
+/// Send a sync request with `fetch`. Only network failures are errors; the
+/// caller reads the status.
+pub async fn send(request: HttpRequest) -> Result<HttpResponse, String> {
+    let headers = Headers::new().map_err(js_error)?;
+    for (name, value) in &request.headers {
+        headers.set(name, value).map_err(js_error)?;
+    }
+    let init = RequestInit::new();
+    init.set_method(request.method.as_str());
+    init.set_headers(&headers);
+    if let Some(body) = &request.body {
+        init.set_body(&JsValue::from_str(body));
+    }
+    let promise = gloo::utils::window().fetch_with_str_and_init(&request.url, &init);
+    let response: Response = JsFuture::from(promise)
+        .await
+        .map_err(js_error)?
+        .dyn_into()
+        .map_err(js_error)?;
+    let body = JsFuture::from(response.text().map_err(js_error)?)
+        .await
+        .map_err(js_error)?;
+    Ok(HttpResponse {
+        status: response.status(),
+        body: body.as_string().unwrap_or_default(),
+    })
+}
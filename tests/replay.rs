@@ -0,0 +1,29 @@
+//! Replays attached to bug reports. Save the code from "Export Replay" as
+//! `tests/replays/<name>.txt` and it is played back here.
+
+use std::fs;
+
+use idle_game::journal::Replay;
+
+#[test]
+fn recorded_replays_play_back() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/replays");
+    let mut played = 0;
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_none_or(|extension| extension != "txt") {
+            continue;
+        }
+        let replay = Replay::import(&fs::read_to_string(&path).unwrap())
+            .unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        // Replays are recorded with exact decimals; `BigFloat` rounds along
+        // the way, so that build only checks they still play.
+        if cfg!(feature = "big-float") {
+            replay.journal().unwrap().replay();
+        } else {
+            replay.verify().unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        }
+        played += 1;
+    }
+    assert!(played > 0);
+}
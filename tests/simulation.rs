@@ -0,0 +1,116 @@
+//! Property tests that drive the game through its public API, the same way the
+//! browser shell does, with a manual clock and in-memory storage.
+
+use std::rc::Rc;
+
+use idle_game::achievements::ACHIEVEMENTS;
+use idle_game::env::{Clock, Env, ManualClock};
+use idle_game::game::{reducer, BuyAmount, Msg, Purchase, State};
+use idle_game::generators::GENERATORS;
+use idle_game::number::Number;
+use idle_game::upgrades::UPGRADE_TREE;
+use proptest::prelude::*;
+
+fn arb_state() -> impl Strategy<Value = State> {
+    (
+        any::<u64>(),
+        prop::collection::vec(0u32..50, GENERATORS.len()),
+        0u32..20,
+    )
+        .prop_map(|(counter, generators, upgrade_level)| State {
+            counter: Number::from(counter),
+            generators,
+            upgrade_level,
+            ..State::new(0.0)
+        })
+}
+
+fn arb_msg() -> impl Strategy<Value = Msg> {
+    prop_oneof![
+        (0u64..120_000).prop_map(Msg::Tick),
+        Just(Msg::UpgradeProduction),
+        (0..GENERATORS.len()).prop_map(Msg::BuyGenerator),
+        Just(Msg::Prestige),
+        Just(Msg::Click),
+        Just(Msg::UpgradeClick),
+        (0..UPGRADE_TREE.len()).prop_map(|i| Msg::BuyUpgrade(UPGRADE_TREE[i].id.to_string())),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn purchases_never_overspend(state in arb_state(), tier in 0..GENERATORS.len()) {
+        let env = Env::in_memory(ManualClock::default());
+        let after = reducer(&state, Msg::BuyGenerator(tier), &env);
+        let price = state.generator_price(tier).unwrap();
+        if state.counter >= price {
+            prop_assert_eq!(&after.counter + &price, state.counter);
+            prop_assert_eq!(after.generators[tier], state.generators[tier] + 1);
+        } else {
+            prop_assert_eq!(after, state);
+        }
+    }
+
+    #[test]
+    fn max_buys_as_many_as_single_purchases(state in arb_state(), tier in 0..GENERATORS.len()) {
+        let env = Env::in_memory(ManualClock::default());
+        let bulk = reducer(&state, Msg::BuyMany(Purchase::Generator(tier), BuyAmount::Max), &env);
+        let mut single = state.clone();
+        while single.can_afford_generator(tier) {
+            single = reducer(&single, Msg::BuyGenerator(tier), &env);
+        }
+        prop_assert_eq!(bulk.generators[tier], single.generators[tier]);
+        prop_assert!(bulk.counter < single.generator_price(tier).unwrap());
+    }
+
+    #[test]
+    fn ticks_add_production(state in arb_state(), ticks in prop::collection::vec(0u64..120_000, 0..100)) {
+        let env = Env::in_memory(ManualClock::default());
+        let mut state = state;
+        for elapsed_ms in ticks {
+            let next = reducer(&state, Msg::Tick(elapsed_ms), &env);
+            let earned = state.production() * Number::from_ratio(elapsed_ms, 1000);
+            prop_assert_eq!(&next.counter, &(&state.counter + earned));
+            state = next;
+        }
+    }
+
+    #[test]
+    fn split_ticks_match_one_long_tick(state in arb_state(), splits in prop::collection::vec(0u64..5_000, 1..20)) {
+        // Without purchases or new achievements production is constant, so
+        // how the time is cut up doesn't matter.
+        let env = Env::in_memory(ManualClock::default());
+        let state = State {
+            achievements: ACHIEVEMENTS.iter().map(|a| a.id.to_string()).collect(),
+            ..state
+        };
+        let total: u64 = splits.iter().sum();
+        let mut split = state.clone();
+        for elapsed_ms in splits {
+            split = reducer(&split, Msg::Tick(elapsed_ms), &env);
+        }
+        let whole = reducer(&state, Msg::Tick(total), &env);
+        prop_assert_eq!(split.counter, whole.counter);
+    }
+
+    #[test]
+    fn lifetime_earnings_never_shrink(msgs in prop::collection::vec(arb_msg(), 0..200)) {
+        let env = Env::in_memory(ManualClock::default());
+        let mut state = State::new(0.0);
+        for msg in msgs {
+            let next = reducer(&state, msg, &env);
+            prop_assert!(next.lifetime_earned >= state.lifetime_earned);
+            state = next;
+        }
+    }
+
+    #[test]
+    fn save_load_round_trip(state in arb_state()) {
+        let clock = Rc::new(ManualClock::default());
+        let env = Env::in_memory(clock.clone());
+        let saved = reducer(&state, Msg::Save, &env);
+        let loaded = State::load(&env, saved.slot).unwrap().unwrap();
+        prop_assert_eq!(&loaded, &saved);
+        prop_assert_eq!(loaded.last_saved_at, Some(clock.now()));
+    }
+}
@@ -0,0 +1,298 @@
+//! The sync protocol between two browsers and a save server, run against an
+//! in-process fake server through the same flow as the sync panel: save,
+//! pull, plan, then push, take the server's game or hand the player a
+//! conflict.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::future::{ready, Future};
+use std::pin::pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use idle_game::env::{Env, ManualClock, MemoryStore, SaveStore};
+use idle_game::error::Error;
+use idle_game::game::{reducer, Msg, State};
+use idle_game::sync::{
+    self, HttpRequest, HttpResponse, Method, PushBody, RemoteSave, SyncConfig, SyncOutcome, SyncedGame, Transport,
+};
+
+const SLOT: usize = 0;
+const TOKEN: &str = "secret";
+
+/// A save server kept in memory, for running the protocol without a network.
+struct FakeSyncServer {
+    token: String,
+    saves: RefCell<HashMap<usize, RemoteSave>>,
+}
+
+impl FakeSyncServer {
+    fn new(token: &str) -> Self {
+        Self {
+            token: token.to_string(),
+            saves: RefCell::new(HashMap::new()),
+        }
+    }
+
+    fn handle(&self, request: &HttpRequest) -> HttpResponse {
+        let respond = |status, body: String| HttpResponse { status, body };
+        if request.header("Authorization") != Some(format!("Bearer {}", self.token).as_str()) {
+            return respond(401, String::new());
+        }
+        let Some(slot) = request
+            .url
+            .rsplit_once("/saves/")
+            .and_then(|(_, slot)| slot.parse().ok())
+        else {
+            return respond(404, String::new());
+        };
+        let mut saves = self.saves.borrow_mut();
+        let current = saves.get(&slot);
+        let to_json = |save: &RemoteSave| serde_json::to_string(save).expect("RemoteSave always serializes");
+        match request.method {
+            Method::Get => match current {
+                Some(save) => respond(200, to_json(save)),
+                None => respond(404, String::new()),
+            },
+            Method::Put => {
+                let Some(body) = request
+                    .body
+                    .as_deref()
+                    .and_then(|body| serde_json::from_str::<PushBody>(body).ok())
+                else {
+                    return respond(400, String::new());
+                };
+                if let Some(current) = current.filter(|current| Some(current.saved_at) != body.base) {
+                    return respond(409, to_json(current));
+                }
+                saves.insert(slot, body.save);
+                respond(204, String::new())
+            }
+        }
+    }
+}
+
+impl Transport for FakeSyncServer {
+    fn send(&self, request: HttpRequest) -> impl Future<Output = Result<HttpResponse, String>> {
+        ready(Ok(self.handle(&request)))
+    }
+}
+
+/// Finish a sync. The fake server answers at once, so it never waits.
+fn finish<T>(future: impl Future<Output = T>) -> T {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(result) => result,
+        Poll::Pending => panic!("the fake server answers at once"),
+    }
+}
+
+/// A browser's storage, which can fill up.
+#[derive(Default)]
+struct Storage {
+    saves: MemoryStore,
+    full: Cell<bool>,
+}
+
+impl SaveStore for Storage {
+    fn get(&self, key: &str) -> Result<Option<String>, String> {
+        self.saves.get(key)
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), String> {
+        if self.full.get() {
+            return Err("QuotaExceededError".to_string());
+        }
+        self.saves.set(key, value)
+    }
+}
+
+/// One browser playing the slot.
+struct Device {
+    env: Env,
+    storage: Rc<Storage>,
+    state: State,
+}
+
+impl SyncedGame for Device {
+    fn env(&self) -> &Env {
+        &self.env
+    }
+
+    fn slot(&self) -> usize {
+        SLOT
+    }
+
+    fn save_game(&mut self) {
+        self.send(Msg::Save);
+    }
+
+    fn load_game(&mut self, save: String) {
+        self.send(Msg::LoadSynced(save));
+    }
+}
+
+impl Device {
+    fn new(clock: &Rc<ManualClock>) -> Self {
+        let storage = Rc::new(Storage::default());
+        Self {
+            env: Env {
+                clock: Box::new(clock.clone()),
+                store: Box::new(storage.clone()),
+            },
+            storage,
+            state: State::new(0.0),
+        }
+    }
+
+    fn send(&mut self, msg: Msg) {
+        self.state = reducer(&self.state, msg, &self.env);
+    }
+
+    /// Earn something and save it.
+    fn play(&mut self, clock: &ManualClock) {
+        clock.advance(1000.0);
+        self.send(Msg::Tick(1000));
+        self.send(Msg::Save);
+    }
+
+    fn sync(&mut self, server: &FakeSyncServer, config: &SyncConfig) -> Result<SyncOutcome, Error> {
+        finish(sync::run(self, config, server))
+    }
+
+    fn push(
+        &self,
+        server: &FakeSyncServer,
+        config: &SyncConfig,
+        local: &RemoteSave,
+        base: Option<f64>,
+    ) -> Result<SyncOutcome, Error> {
+        finish(sync::push(self, config, server, local, base))
+    }
+}
+
+fn setup() -> (FakeSyncServer, SyncConfig, Rc<ManualClock>) {
+    let config = SyncConfig {
+        endpoint: "https://saves.example/api/".to_string(),
+        token: TOKEN.to_string(),
+    };
+    (FakeSyncServer::new(TOKEN), config, Rc::new(ManualClock::new(0.0)))
+}
+
+#[test]
+fn progress_moves_between_browsers() {
+    let (server, config, clock) = setup();
+    let mut laptop = Device::new(&clock);
+    let mut phone = Device::new(&clock);
+
+    laptop.play(&clock);
+    assert_eq!(laptop.sync(&server, &config), Ok(SyncOutcome::Pushed));
+    assert_eq!(laptop.sync(&server, &config), Ok(SyncOutcome::UpToDate));
+
+    // A fresh game has nothing to lose, so it just takes the server's.
+    assert_eq!(phone.sync(&server, &config), Ok(SyncOutcome::Pulled));
+    assert_eq!(phone.state.lifetime_earned, laptop.state.lifetime_earned);
+
+    phone.play(&clock);
+    assert_eq!(phone.sync(&server, &config), Ok(SyncOutcome::Pushed));
+    let before = laptop.state.lifetime_earned.clone();
+    assert_eq!(laptop.sync(&server, &config), Ok(SyncOutcome::Pulled));
+    assert_eq!(laptop.state.lifetime_earned, phone.state.lifetime_earned);
+
+    // Taking the server's game can be undone like a load.
+    laptop.send(Msg::Undo);
+    assert_eq!(laptop.state.lifetime_earned, before);
+}
+
+#[test]
+fn diverged_games_are_the_players_choice() {
+    let (server, config, clock) = setup();
+    let mut laptop = Device::new(&clock);
+    let mut phone = Device::new(&clock);
+    laptop.play(&clock);
+    laptop.sync(&server, &config).unwrap();
+    phone.sync(&server, &config).unwrap();
+
+    laptop.play(&clock);
+    laptop.play(&clock);
+    laptop.sync(&server, &config).unwrap();
+    phone.play(&clock);
+    let Ok(SyncOutcome::Conflict { remote, .. }) = phone.sync(&server, &config) else {
+        panic!("both games moved on");
+    };
+    let local = sync::save_now(&mut phone).unwrap();
+    assert!(!sync::suggest_local(&local, &remote));
+
+    // Keeping the phone's game replaces the laptop's on the server...
+    assert_eq!(
+        phone.push(&server, &config, &local, Some(remote.saved_at)),
+        Ok(SyncOutcome::Pushed)
+    );
+    laptop.play(&clock);
+    let Ok(SyncOutcome::Conflict { remote, .. }) = laptop.sync(&server, &config) else {
+        panic!("the laptop's game moved on too");
+    };
+    // ...and the laptop can still take it.
+    assert_eq!(sync::take(&mut laptop, &remote), Ok(SyncOutcome::Pulled));
+    assert_eq!(laptop.state.last_saved_at, phone.state.last_saved_at);
+    // The second the laptop's game spent away counts as progress of its own.
+    assert_eq!(laptop.sync(&server, &config), Ok(SyncOutcome::Pushed));
+    assert_eq!(phone.sync(&server, &config), Ok(SyncOutcome::Pulled));
+}
+
+#[test]
+fn unsaved_progress_is_not_pulled_over() {
+    let (server, config, clock) = setup();
+    let mut laptop = Device::new(&clock);
+    let mut phone = Device::new(&clock);
+    laptop.play(&clock);
+    laptop.sync(&server, &config).unwrap();
+    phone.sync(&server, &config).unwrap();
+    laptop.play(&clock);
+    laptop.sync(&server, &config).unwrap();
+
+    // The phone earned something it never saved; the sync saves it first.
+    clock.advance(1000.0);
+    phone.send(Msg::Tick(1000));
+    assert!(matches!(phone.sync(&server, &config), Ok(SyncOutcome::Conflict { .. })));
+}
+
+#[test]
+fn a_failed_save_stops_the_sync() {
+    let (server, config, clock) = setup();
+    let mut laptop = Device::new(&clock);
+    laptop.play(&clock);
+    laptop.sync(&server, &config).unwrap();
+
+    // The save from before is still there, but a new one can't be written.
+    laptop.play(&clock);
+    laptop.storage.full.set(true);
+    clock.advance(1000.0);
+    laptop.send(Msg::Tick(1000));
+    assert_eq!(laptop.sync(&server, &config), Err(Error::NotSaved));
+}
+
+#[test]
+fn pushes_over_an_unseen_save_are_rejected() {
+    let (server, config, clock) = setup();
+    let mut laptop = Device::new(&clock);
+    let mut phone = Device::new(&clock);
+    laptop.play(&clock);
+    laptop.sync(&server, &config).unwrap();
+
+    // The phone never pulled, so it can't replace what it hasn't seen.
+    phone.play(&clock);
+    let local = sync::save_now(&mut phone).unwrap();
+    let outcome = phone.push(&server, &config, &local, None).unwrap();
+    assert!(matches!(outcome, SyncOutcome::Conflict { remote, .. } if remote.lifetime_earned == laptop.state.lifetime_earned));
+}
+
+#[test]
+fn a_wrong_token_is_reported() {
+    let (server, config, clock) = setup();
+    let mut laptop = Device::new(&clock);
+    let config = SyncConfig {
+        token: "guess".to_string(),
+        ..config
+    };
+    assert_eq!(laptop.sync(&server, &config), Err(Error::SyncTokenRejected));
+}